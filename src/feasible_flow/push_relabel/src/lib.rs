@@ -0,0 +1,203 @@
+use num_traits::NumAssign;
+use std::collections::VecDeque;
+
+pub trait Flow: Copy + Ord + NumAssign {}
+
+impl<T: Copy + Ord + NumAssign> Flow for T {}
+
+#[derive(Clone)]
+struct InternalEdge<F: Flow> {
+    to: usize,
+    rev: usize, // 逆辺のindex. graph[to][rev]でアクセスできる
+    flow: F,
+    upper: F,
+}
+
+impl<F: Flow> InternalEdge<F> {
+    pub fn new(to: usize, rev: usize, flow: F, upper: F) -> Self {
+        InternalEdge { to, rev, flow, upper }
+    }
+
+    pub fn residual_capacity(&self) -> F {
+        self.upper - self.flow
+    }
+}
+
+// find a flow satisfying lower <= flow <= upper on every edge and the supply of every node
+pub struct LowerBound<F: Flow> {
+    num_of_nodes: usize,
+    edges: Vec<(usize, usize, F, F)>, // (from, to, lower, upper)
+    supply: Vec<F>,
+}
+
+impl<F: Flow> LowerBound<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        LowerBound { num_of_nodes, edges: Vec::new(), supply: vec![F::zero(); num_of_nodes] }
+    }
+
+    // returns the index of the edge in the vector returned by solve
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: F, upper: F) -> usize {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        self.edges.push((from, to, lower, upper));
+        self.edges.len() - 1
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.supply[node] += supply;
+    }
+
+    // returns the flow of each edge, or None if there is no feasible flow
+    pub fn solve(&self) -> Option<Vec<F>> {
+        let mut total = F::zero();
+        for u in 0..self.num_of_nodes {
+            total += self.supply[u];
+        }
+        if total != F::zero() {
+            return None;
+        }
+
+        // 下界の分だけ先に流しておき，残りをsource -> sinkの最大流で求める
+        let source = self.num_of_nodes;
+        let sink = self.num_of_nodes + 1;
+        let mut solver = MaximumFlow::new(self.num_of_nodes + 2);
+
+        let mut excess = self.supply.clone();
+        let mut positions = Vec::with_capacity(self.edges.len());
+        for &(from, to, lower, upper) in self.edges.iter() {
+            positions.push(solver.add_edge(from, to, upper - lower));
+            excess[from] -= lower;
+            excess[to] += lower;
+        }
+
+        let mut required = F::zero();
+        for (u, &e) in excess.iter().enumerate() {
+            if e > F::zero() {
+                solver.add_edge(source, u, e);
+                required += e;
+            } else if e < F::zero() {
+                solver.add_edge(u, sink, F::zero() - e);
+            }
+        }
+
+        if solver.solve(source, sink) != required {
+            return None;
+        }
+
+        let mut flows = Vec::with_capacity(self.edges.len());
+        for (i, &(_, _, lower, _)) in self.edges.iter().enumerate() {
+            flows.push(lower + solver.flow(positions[i]));
+        }
+        Some(flows)
+    }
+}
+
+// FIFO push relabel
+struct MaximumFlow<F: Flow> {
+    num_of_nodes: usize,
+    graph: Vec<Vec<InternalEdge<F>>>,
+    excess: Vec<F>,
+    heights: Vec<usize>,
+    current_edges: Vec<usize>,
+    active_nodes: VecDeque<usize>,
+}
+
+impl<F: Flow> MaximumFlow<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        MaximumFlow {
+            num_of_nodes,
+            graph: vec![vec![]; num_of_nodes],
+            excess: vec![F::zero(); num_of_nodes],
+            heights: vec![0; num_of_nodes],
+            current_edges: vec![0; num_of_nodes],
+            active_nodes: VecDeque::new(),
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, upper: F) -> (usize, usize) {
+        let e = self.graph[from].len();
+        let re = if from == to { e + 1 } else { self.graph[to].len() };
+
+        self.graph[from].push(InternalEdge::new(to, re, F::zero(), upper));
+        self.graph[to].push(InternalEdge::new(from, e, F::zero(), F::zero()));
+
+        (from, e)
+    }
+
+    fn flow(&self, position: (usize, usize)) -> F {
+        self.graph[position.0][position.1].flow
+    }
+
+    fn solve(&mut self, source: usize, sink: usize) -> F {
+        self.heights[source] = self.num_of_nodes;
+        for i in 0..self.graph[source].len() {
+            let flow = self.graph[source][i].residual_capacity();
+            self.push_flow(source, i, flow);
+        }
+
+        for u in 0..self.num_of_nodes {
+            if u != source && u != sink && self.excess[u] > F::zero() {
+                self.active_nodes.push_back(u);
+            }
+        }
+
+        while let Some(u) = self.active_nodes.pop_front() {
+            self.discharge(u, source, sink);
+        }
+
+        self.excess[sink]
+    }
+
+    fn discharge(&mut self, u: usize, source: usize, sink: usize) {
+        while self.excess[u] > F::zero() {
+            if self.current_edges[u] == self.graph[u].len() {
+                self.relabel(u);
+                continue;
+            }
+
+            let i = self.current_edges[u];
+            let edge = &self.graph[u][i];
+            let to = edge.to;
+            if edge.residual_capacity() > F::zero() && self.heights[u] == self.heights[to] + 1 {
+                let flow = F::min(edge.residual_capacity(), self.excess[u]);
+                // toが新たにactiveになった
+                if to != source && to != sink && self.excess[to] <= F::zero() && self.excess[to] + flow > F::zero() {
+                    self.active_nodes.push_back(to);
+                }
+                self.push_flow(u, i, flow);
+            } else {
+                self.current_edges[u] += 1;
+            }
+        }
+    }
+
+    fn relabel(&mut self, u: usize) {
+        let mut new_height = usize::MAX;
+        for edge in self.graph[u].iter() {
+            if edge.residual_capacity() > F::zero() {
+                new_height = usize::min(new_height, self.heights[edge.to] + 1);
+            }
+        }
+        // excessがあるnodeは必ずsourceに戻る残余パスをもつ
+        assert_ne!(new_height, usize::MAX);
+
+        self.heights[u] = new_height;
+        self.current_edges[u] = 0;
+    }
+
+    fn push_flow(&mut self, u: usize, i: usize, flow: F) {
+        if flow == F::zero() {
+            return;
+        }
+
+        let to = self.graph[u][i].to;
+        let rev = self.graph[u][i].rev;
+
+        self.graph[u][i].flow += flow;
+        self.graph[to][rev].flow -= flow;
+        self.excess[u] -= flow;
+        self.excess[to] += flow;
+    }
+}
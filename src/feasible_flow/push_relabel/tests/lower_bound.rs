@@ -0,0 +1,102 @@
+use push_relabel::LowerBound;
+use test_support::{brute_force, random_problem, Random};
+
+// (from, to, lower, upper)
+type Edges<'a> = &'a [(usize, usize, i64, i64)];
+
+// the flows are within the bounds and meet the supplies
+fn assert_feasible(edges: Edges, supply: &[i64], flows: &[i64]) {
+    assert_eq!(flows.len(), edges.len());
+    let mut balance = supply.to_vec();
+    for (&(from, to, lower, upper), &flow) in edges.iter().zip(flows.iter()) {
+        assert!(lower <= flow && flow <= upper, "flow {} of {} -> {} is out of [{}, {}]", flow, from, to, lower, upper);
+        balance[from] -= flow;
+        balance[to] += flow;
+    }
+    assert!(balance.iter().all(|&b| b == 0), "balance {:?}", balance);
+}
+
+fn solve(edges: Edges, supply: &[i64]) -> Option<Vec<i64>> {
+    let mut solver = LowerBound::new(supply.len());
+    for (i, &(from, to, lower, upper)) in edges.iter().enumerate() {
+        assert_eq!(solver.add_edge(from, to, lower, upper), i);
+    }
+    for (u, &b) in supply.iter().enumerate() {
+        solver.add_supply(u, b);
+    }
+    let flows = solver.solve();
+    if let Some(flows) = flows.as_ref() {
+        assert_feasible(edges, supply, flows);
+    }
+    flows
+}
+
+#[test]
+fn finds_feasible_flows() {
+    // 3 units from 0 to 2, at least 2 of them through 1
+    let edges = [(0, 1, 2, 5), (1, 2, 0, 3), (0, 2, 0, 1)];
+    assert!(solve(&edges, &[3, 0, -3]).is_some());
+    assert_eq!(solve(&edges, &[2, 0, -2]), Some(vec![2, 2, 0]));
+
+    // a lower bound on a cycle needs a circulation
+    assert_eq!(solve(&[(0, 1, 1, 1), (1, 2, 0, 4), (2, 0, 0, 4)], &[0, 0, 0]), Some(vec![1, 1, 1]));
+
+    // no nodes and no edges
+    assert_eq!(solve(&[], &[]), Some(vec![]));
+}
+
+#[test]
+fn detects_infeasible_flows() {
+    let cases: [(Edges, &[i64]); 4] = [
+        // the lower bound has no way back
+        (&[(0, 1, 1, 2)], &[0, 0]),
+        // too little capacity
+        (&[(0, 1, 0, 2), (1, 2, 0, 1)], &[2, 0, -2]),
+        // more than the upper bounds leave room for
+        (&[(0, 1, 3, 5), (1, 2, 0, 2)], &[3, 0, -3]),
+        // no edge into 2
+        (&[(0, 1, 0, 5)], &[1, 0, -1]),
+    ];
+    for &(edges, supply) in cases.iter() {
+        assert_eq!(solve(edges, supply), None, "{:?} {:?}", edges, supply);
+    }
+}
+
+#[test]
+fn handles_negative_lower_bounds() {
+    // a negative flow goes backwards: 2 units from 1 to 0 along 0 -> 1
+    assert_eq!(solve(&[(0, 1, -3, -1)], &[-2, 2]), Some(vec![-2]));
+    assert_eq!(solve(&[(0, 1, -3, -1)], &[-4, 4]), None);
+    // either direction
+    assert_eq!(solve(&[(0, 1, -2, 2)], &[1, -1]), Some(vec![1]));
+    assert_eq!(solve(&[(0, 1, -2, 2)], &[-2, 2]), Some(vec![-2]));
+    assert!(solve(&[(0, 1, -2, 2), (1, 0, -1, 0)], &[0, 0]).is_some());
+}
+
+#[test]
+fn handles_self_loops() {
+    // a self loop meets its own lower bound, but carries nothing elsewhere
+    let flows = solve(&[(0, 0, 2, 4), (1, 1, -3, -1)], &[0, 0]).unwrap();
+    assert!(2 <= flows[0] && flows[0] <= 4 && -3 <= flows[1] && flows[1] <= -1);
+    assert_eq!(solve(&[(0, 0, 0, 5)], &[1, -1]), None);
+    assert!(solve(&[(0, 0, 0, 5), (0, 1, 1, 1)], &[1, -1]).is_some());
+}
+
+#[test]
+fn rejects_unbalanced_supplies() {
+    assert_eq!(solve(&[(0, 1, 0, 5)], &[2, -1]), None);
+    assert_eq!(solve(&[], &[1]), None);
+    assert_eq!(solve(&[(0, 1, -5, 5), (1, 0, -5, 5)], &[0, -1]), None);
+}
+
+#[test]
+fn matches_brute_force() {
+    // a feasible flow exists if and only if there is an optimal one
+    let mut random = Random(0x5851f42d4c957f2d);
+    for case in 0..20000 {
+        let problem = random_problem(&mut random);
+        let edges: Vec<_> = problem.edges.iter().map(|e| (e.from, e.to, e.lower, e.upper)).collect();
+        let feasible = solve(&edges, &problem.supply).is_some();
+        assert_eq!(feasible, brute_force(&problem).1.is_some(), "case {}: {:?}", case, problem);
+    }
+}
@@ -0,0 +1,342 @@
+#![allow(clippy::needless_range_loop)]
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub use minimum_cost_flow_common::{Edge, EdgeId, Flow, MinCostFlowSolver, Status};
+
+#[derive(Clone)]
+struct InternalEdge<F: Flow> {
+    to: usize,
+    rev: usize, // 逆辺のindex. graph[to][rev]でアクセスできる
+    flow: F,
+    lower: F,
+    upper: F,
+    cost: F,
+}
+
+impl<F: Flow> InternalEdge<F> {
+    pub fn new(to: usize, rev: usize, flow: F, lower: F, upper: F, cost: F) -> Self {
+        InternalEdge { to, rev, flow, lower, upper, cost }
+    }
+
+    pub fn residual_capacity(&self) -> F {
+        self.upper - self.flow
+    }
+}
+
+// successive shortest path on the delta-residual network, halving delta in each phase
+pub struct CapacityScaling<F: Flow> {
+    num_of_nodes: usize,
+    graph: Vec<Vec<InternalEdge<F>>>,
+    edges: Vec<(usize, usize)>, // graph[u][i] of each edge in the order of addition
+
+    // Node
+    initial_excess: Vec<F>,
+    excess: Vec<F>,
+    potentials: Vec<F>,
+
+    // Edge
+    is_rev: Vec<Vec<bool>>,
+
+    // status
+    status: Status,
+    optimal_cost: Option<i128>,
+    num_augment: u64,
+
+    // settings
+    scaling_factor: F,
+}
+
+impl<F: Flow> CapacityScaling<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        CapacityScaling {
+            num_of_nodes,
+            graph: vec![vec![]; num_of_nodes],
+            edges: Vec::new(),
+
+            initial_excess: vec![F::zero(); num_of_nodes],
+            excess: vec![F::zero(); num_of_nodes],
+            potentials: vec![F::zero(); num_of_nodes],
+
+            is_rev: vec![vec![]; num_of_nodes],
+
+            status: Status::NotSolved,
+            optimal_cost: None,
+            num_augment: 0,
+
+            scaling_factor: F::from_i32(2).unwrap(),
+        }
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        let e = self.graph[from].len();
+        let re = if from == to { e + 1 } else { self.graph[to].len() };
+
+        let e1 = InternalEdge::new(to, re, F::zero(), lower, upper, cost);
+        self.graph[from].push(e1);
+        self.is_rev[from].push(false);
+
+        let e2 = InternalEdge::new(from, e, F::zero(), F::zero(), -lower, -cost);
+        self.graph[to].push(e2);
+        self.is_rev[to].push(true);
+
+        self.edges.push((from, e));
+        EdgeId::new(self.edges.len() - 1)
+    }
+
+    pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        let (u, i) = self.edges[edge_id.index()];
+        let e = &self.graph[u][i];
+        Edge { from: u, to: e.to, flow: e.flow, lower: e.lower, upper: e.upper, cost: e.cost }
+    }
+
+    pub fn get_potential(&self) -> Vec<F> {
+        self.potentials.clone()
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.initial_excess[node] += supply;
+    }
+
+    pub fn set_scaling_factor(&mut self, scaling_factor: F) {
+        assert!(scaling_factor >= F::from_i32(2).unwrap());
+        self.scaling_factor = scaling_factor;
+    }
+
+    pub fn solve(&mut self) -> Status {
+        self.status = Status::NotSolved;
+        self.optimal_cost = None;
+
+        if self.num_of_nodes == 0 {
+            self.status = Status::Optimal;
+            self.optimal_cost = Some(0);
+            return Status::Optimal;
+        }
+
+        if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
+            return Status::Unbalanced;
+        }
+
+        let mut delta = self.initialize();
+        loop {
+            self.saturate_negative_edges(delta);
+            while self.augment(delta) {}
+
+            if delta == F::one() {
+                break;
+            }
+            delta = F::max(delta / self.scaling_factor, F::one());
+        }
+
+        // delta = 1 の残余ネットワークで余剰を流しきれなかった
+        for u in 0..self.num_of_nodes {
+            if self.excess[u] != F::zero() {
+                self.status = Status::Infeasible;
+                return Status::Infeasible;
+            }
+        }
+
+        let mut cost = 0;
+        for u in 0..self.num_of_nodes {
+            for (i, edge) in self.graph[u].iter().enumerate() {
+                if !self.is_rev[u][i] {
+                    cost += F::to_i128(&edge.flow).unwrap() * F::to_i128(&edge.cost).unwrap();
+                }
+            }
+        }
+        self.optimal_cost = Some(cost);
+
+        self.status = Status::Optimal;
+        Status::Optimal
+    }
+
+    pub fn optimal_cost(&self) -> Option<i128> {
+        self.optimal_cost
+    }
+
+    pub fn num_augment(&self) -> u64 {
+        self.num_augment
+    }
+
+    fn is_unbalanced(&self) -> bool {
+        let mut total = F::zero();
+        for u in 0..self.num_of_nodes {
+            total += self.initial_excess[u];
+        }
+        total != F::zero()
+    }
+
+    // returns the initial delta
+    fn initialize(&mut self) -> F {
+        self.excess = self.initial_excess.clone();
+        self.potentials = vec![F::zero(); self.num_of_nodes];
+        self.num_augment = 0;
+
+        // 下界の分だけ先に流しておく
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                self.graph[u][i].flow = F::zero();
+            }
+        }
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                if !self.is_rev[u][i] {
+                    let flow = self.graph[u][i].lower;
+                    self.push_flow(u, i, flow);
+                }
+            }
+        }
+
+        // largest power of scaling_factor not exceeding any capacity or excess
+        let mut maxi = F::zero();
+        for u in 0..self.num_of_nodes {
+            maxi = F::max(maxi, F::max(self.excess[u], -self.excess[u]));
+            for edge in self.graph[u].iter() {
+                maxi = F::max(maxi, edge.residual_capacity());
+            }
+        }
+
+        let mut delta = F::one();
+        while delta <= maxi / self.scaling_factor {
+            delta *= self.scaling_factor;
+        }
+        delta
+    }
+
+    // delta-残余ネットワークでreduced costが負の辺を飽和させる
+    fn saturate_negative_edges(&mut self, delta: F) {
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                let edge = &self.graph[u][i];
+                if edge.residual_capacity() >= delta && self.reduced_cost(u, edge) < F::zero() {
+                    let flow = edge.residual_capacity();
+                    self.push_flow(u, i, flow);
+                }
+            }
+        }
+    }
+
+    // find a shortest path in the delta-residual network from a node with excess >= delta to a node with excess <= -delta
+    // returns false if there is no such path
+    fn augment(&mut self, delta: F) -> bool {
+        let inf = F::max_value();
+        let mut dist = vec![inf; self.num_of_nodes];
+        let mut prev: Vec<Option<(usize, usize)>> = vec![None; self.num_of_nodes];
+        let mut heap = BinaryHeap::new();
+
+        for u in 0..self.num_of_nodes {
+            if self.excess[u] >= delta {
+                dist[u] = F::zero();
+                heap.push(Reverse((F::zero(), u)));
+            }
+        }
+
+        // dijkstra with reduced costs
+        let mut sink = None;
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if d > dist[u] {
+                continue;
+            }
+            if self.excess[u] <= -delta {
+                sink = Some(u);
+                break;
+            }
+
+            for (i, edge) in self.graph[u].iter().enumerate() {
+                if edge.residual_capacity() < delta {
+                    continue;
+                }
+                let new_dist = d + self.reduced_cost(u, edge);
+                if new_dist < dist[edge.to] {
+                    dist[edge.to] = new_dist;
+                    prev[edge.to] = Some((u, i));
+                    heap.push(Reverse((new_dist, edge.to)));
+                }
+            }
+        }
+
+        let t = match sink {
+            Some(t) => t,
+            None => return false,
+        };
+
+        // potentialを更新しても，delta-残余ネットワークのreduced costは非負のまま
+        for u in 0..self.num_of_nodes {
+            self.potentials[u] += F::min(dist[u], dist[t]);
+        }
+
+        let mut flow = -self.excess[t];
+        let mut v = t;
+        while let Some((u, i)) = prev[v] {
+            flow = F::min(flow, self.graph[u][i].residual_capacity());
+            v = u;
+        }
+        flow = F::min(flow, self.excess[v]);
+
+        let mut v = t;
+        while let Some((u, i)) = prev[v] {
+            self.push_flow(u, i, flow);
+            v = u;
+        }
+        self.num_augment += 1;
+        true
+    }
+
+    fn push_flow(&mut self, u: usize, i: usize, flow: F) {
+        if flow == F::zero() {
+            return;
+        }
+
+        let to = self.graph[u][i].to;
+        let from = u;
+        let rev = self.graph[u][i].rev;
+
+        self.graph[u][i].flow += flow;
+        self.graph[to][rev].flow -= flow;
+        self.excess[from] -= flow;
+        self.excess[to] += flow;
+    }
+
+    fn reduced_cost(&self, u: usize, edge: &InternalEdge<F>) -> F {
+        edge.cost + self.potentials[u] - self.potentials[edge.to]
+    }
+}
+
+impl<F: Flow> MinCostFlowSolver<F> for CapacityScaling<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        CapacityScaling::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        CapacityScaling::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        CapacityScaling::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        CapacityScaling::solve(self)
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        CapacityScaling::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        CapacityScaling::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        CapacityScaling::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{Edge, EdgeId, Flow, MinCostFlowProblem, Solution, Status};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// a path from a node with supply to a node with demand, or a cycle, that carries amount
+// edges[i] goes from nodes[i] to nodes[i + 1], backwards if its flow is negative. a cycle ends where it starts
+// cost is the cost of the amount along the path
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FlowPath<F: Flow> {
+    pub nodes: Vec<usize>,
+    pub edges: Vec<EdgeId>,
+    pub amount: F,
+    pub cost: i128,
+}
+
+impl<F: Flow> FlowPath<F> {
+    pub fn is_cycle(&self) -> bool {
+        self.nodes.first() == self.nodes.last()
+    }
+}
+
+// flow decomposition of the flows of edges[i] (EdgeId::new(i)) that satisfy the supplies
+// every removed path or cycle empties an edge, the supply of its first node or the demand of its last node, and
+// lowers the cyclomatic number of the flow with an extra node joined to the nodes with supply or demand, so there
+// are at most as many paths and cycles as edges with flow
+// the order is deterministic: the self loops, then the paths from the nodes with supply in the order of the
+// nodes, and the cycles found on the way, then the remaining cycles in the order of their smallest edge.
+// walks always leave a node by its first edge with remaining flow
+pub fn decompose_flow<F: Flow>(edges: &[Edge<F>], supply: &[F]) -> Vec<FlowPath<F>> {
+    let n = supply.len();
+    let mut balance: Vec<i128> = supply.iter().map(|s| F::to_i128(s).unwrap()).collect();
+    for e in edges.iter() {
+        balance[e.from] -= F::to_i128(&e.flow).unwrap();
+        balance[e.to] += F::to_i128(&e.flow).unwrap();
+    }
+    assert!(balance.iter().all(|&b| b == 0), "the flow does not satisfy the supplies");
+
+    let mut decomposition = Decomposition::new(edges, n);
+    let mut balance = supply.to_vec();
+    for s in 0..n {
+        while balance[s] > F::zero() {
+            let walk = decomposition.walk(s, |u, _| balance[u] < F::zero());
+            let t = *walk.nodes.last().unwrap();
+            let amount = walk.edges.iter().fold(F::min(balance[s], -balance[t]), |amount, &i| F::min(amount, decomposition.remaining[i]));
+            balance[s] -= amount;
+            balance[t] += amount;
+            decomposition.remove(walk, amount);
+        }
+    }
+
+    for i in 0..edges.len() {
+        while decomposition.remaining[i] > F::zero() {
+            // the walk leaves by edge i, the first edge with flow, so the cycle through it ends the walk
+            let u = decomposition.tail(i);
+            decomposition.walk(u, |_, remaining| remaining[i] == F::zero());
+        }
+    }
+    decomposition.paths
+}
+
+struct Walk {
+    nodes: Vec<usize>,
+    edges: Vec<usize>,
+}
+
+struct Decomposition<'a, F: Flow> {
+    edges: &'a [Edge<F>],
+    remaining: Vec<F>,
+    out: Vec<Vec<usize>>, // the edges with flow out of each node
+    current: Vec<usize>,  // edges before out[u][current[u]] have no remaining flow
+    position: Vec<Option<usize>>,
+    paths: Vec<FlowPath<F>>,
+}
+
+impl<'a, F: Flow> Decomposition<'a, F> {
+    fn new(edges: &'a [Edge<F>], n: usize) -> Self {
+        let remaining = edges.iter().map(|e| if e.flow < F::zero() { -e.flow } else { e.flow }).collect();
+        let mut decomposition = Decomposition { edges, remaining, out: vec![Vec::new(); n], current: vec![0; n], position: vec![None; n], paths: Vec::new() };
+        for (i, e) in edges.iter().enumerate() {
+            if e.flow == F::zero() {
+                continue;
+            }
+            if e.from == e.to {
+                let amount = decomposition.remaining[i];
+                decomposition.remove(Walk { nodes: vec![e.from, e.to], edges: vec![i] }, amount);
+            } else {
+                let u = decomposition.tail(i);
+                decomposition.out[u].push(i);
+            }
+        }
+        decomposition
+    }
+
+    fn tail(&self, i: usize) -> usize {
+        let e = &self.edges[i];
+        if e.flow < F::zero() {
+            e.to
+        } else {
+            e.from
+        }
+    }
+
+    fn head(&self, i: usize) -> usize {
+        let e = &self.edges[i];
+        if e.flow < F::zero() {
+            e.from
+        } else {
+            e.to
+        }
+    }
+
+    // follows the flow from s until stop is true for the last node and the remaining flows, and removes
+    // the cycles on the way. a node with more inflow than outflow must stop the walk
+    fn walk<P: Fn(usize, &[F]) -> bool>(&mut self, s: usize, stop: P) -> Walk {
+        let mut walk = Walk { nodes: vec![s], edges: Vec::new() };
+        self.position[s] = Some(0);
+        loop {
+            let u = *walk.nodes.last().unwrap();
+            if stop(u, &self.remaining) {
+                break;
+            }
+            while self.current[u] < self.out[u].len() && self.remaining[self.out[u][self.current[u]]] == F::zero() {
+                self.current[u] += 1;
+            }
+            let i = self.out[u][self.current[u]];
+            let v = self.head(i);
+            walk.edges.push(i);
+            match self.position[v] {
+                Some(k) => {
+                    let cycle = Walk { nodes: walk.nodes[k..].iter().copied().chain(Some(v)).collect(), edges: walk.edges.split_off(k) };
+                    for &w in walk.nodes[k + 1..].iter() {
+                        self.position[w] = None;
+                    }
+                    walk.nodes.truncate(k + 1);
+                    let amount = cycle.edges.iter().map(|&i| self.remaining[i]).min().unwrap();
+                    self.remove(cycle, amount);
+                }
+                None => {
+                    self.position[v] = Some(walk.nodes.len());
+                    walk.nodes.push(v);
+                }
+            }
+        }
+        for &w in walk.nodes.iter() {
+            self.position[w] = None;
+        }
+        walk
+    }
+
+    fn remove(&mut self, walk: Walk, amount: F) {
+        let mut cost = 0;
+        for &i in walk.edges.iter() {
+            self.remaining[i] -= amount;
+            let e = &self.edges[i];
+            cost += if e.flow < F::zero() { -F::to_i128(&e.cost).unwrap() } else { F::to_i128(&e.cost).unwrap() };
+        }
+        let edges = walk.edges.into_iter().map(EdgeId::new).collect();
+        self.paths.push(FlowPath { nodes: walk.nodes, edges, amount, cost: cost * F::to_i128(&amount).unwrap() });
+    }
+}
+
+impl<F: Flow> MinCostFlowProblem<F> {
+    // the flow of an optimal solution as paths and cycles (see decompose_flow)
+    pub fn decompose(&self, solution: &Solution<F>) -> Vec<FlowPath<F>> {
+        assert_eq!(solution.status, Status::Optimal);
+        let edges: Vec<Edge<F>> = self.edges.iter().zip(solution.flows.iter()).map(|(e, &flow)| Edge::new(e.from, e.to, flow, e.lower, e.upper, e.cost)).collect();
+        decompose_flow(&edges, &self.supply)
+    }
+}
@@ -0,0 +1,151 @@
+#![allow(clippy::needless_range_loop)]
+use num::{CheckedMul, FromPrimitive, ToPrimitive};
+use num_traits::NumAssign;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+use std::ops::Neg;
+
+mod decomposition;
+mod problem;
+mod sensitivity;
+
+// serde schema (feature "serde"), shown as JSON
+// MinCostFlowProblem: {"num_of_nodes": 2, "edges": [{"from": 0, "to": 1, "lower": 0, "upper": 5, "cost": 3, "name": "e0"}], "supply": [5, -5], "node_names": ["s", null]}
+//     name is null for edges without a name. name, supply and node_names may be omitted. nodes are numbered from 0
+// MaxFlowProblem: {"num_of_nodes": 2, "source": 0, "sink": 1, "edges": [[0, 1, 5]]}
+//     edges are [from, to, capacity]
+// CostRange: {"lower": -3, "upper": null}
+//     null is unbounded
+// Solution: {"status": "Optimal", "flows": [5], "potentials": [0, 3], "cost": 15}
+// FlowPath: {"nodes": [0, 1, 2], "edges": [0, 3], "amount": 2, "cost": 8}
+// Edge: {"from": 0, "to": 1, "flow": 5, "lower": 0, "upper": 5, "cost": 3}
+// EdgeId: the index of the edge, e.g. 0
+// Status: the name of the variant, e.g. "Infeasible"
+// deserializing a problem checks the node ids and the bounds
+
+pub use decomposition::{decompose_flow, FlowPath};
+pub use problem::{MaxFlowProblem, MinCostFlowProblem, ProblemEdge, Solution};
+pub use sensitivity::CostRange;
+
+pub trait Flow: 'static + Copy + Ord + Display + Debug + BoundedBelow + BoundedAbove + FromPrimitive + ToPrimitive + NumAssign + CheckedMul + Neg<Output = Self> {}
+
+pub trait Zero {
+    fn zero() -> Self;
+}
+
+pub trait One {
+    fn one() -> Self;
+}
+
+pub trait BoundedBelow {
+    fn min_value() -> Self;
+}
+
+pub trait BoundedAbove {
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_integral {
+    ($($ty:ty),*) => {
+        $(
+            impl Zero for $ty {
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+            }
+
+            impl One for $ty {
+                #[inline]
+                fn one() -> Self {
+                    1
+                }
+            }
+
+            impl BoundedBelow for $ty {
+                #[inline]
+                fn min_value() -> Self {
+                    Self::MIN
+                }
+            }
+
+            impl BoundedAbove for $ty {
+                #[inline]
+                fn max_value() -> Self {
+                    Self::MAX
+                }
+            }
+
+            impl Flow for $ty {}
+        )*
+    };
+}
+
+impl_integral!(i8, i16, i32, i64, i128);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Status {
+    NotSolved,
+    Optimal,
+    Feasible,
+    Infeasible,
+    Unbalanced,
+    BadResult,
+    BadCostRange,
+}
+
+// edges are numbered in the order they are added to a solver
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    pub fn new(index: usize) -> Self {
+        EdgeId(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Edge<F: Flow> {
+    pub from: usize,
+    pub to: usize,
+    pub flow: F,
+    pub lower: F,
+    pub upper: F,
+    pub cost: F,
+}
+
+impl<F: Flow> Edge<F> {
+    pub fn new(from: usize, to: usize, flow: F, lower: F, upper: F, cost: F) -> Self {
+        Edge { from, to, flow, lower, upper, cost }
+    }
+}
+
+// common interface of the minimum cost flow solvers
+// potentials satisfy cost + potential[from] - potential[to] >= 0 for every edge of the residual network
+pub trait MinCostFlowSolver<F: Flow> {
+    fn new(num_of_nodes: usize) -> Self
+    where
+        Self: Sized;
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId;
+
+    fn add_supply(&mut self, node: usize, supply: F);
+
+    fn solve(&mut self) -> Status;
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F>;
+
+    fn get_potential(&self) -> Vec<F>;
+
+    fn optimal_cost(&self) -> Option<i128>;
+
+    fn status(&self) -> Status;
+}
@@ -0,0 +1,285 @@
+use crate::{EdgeId, Flow, MinCostFlowSolver, Status};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProblemEdge<F: Flow> {
+    pub from: usize,
+    pub to: usize,
+    pub lower: F,
+    pub upper: F,
+    pub cost: F,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+}
+
+// a minimum cost flow instance independent of any solver
+// edge i of the problem corresponds to EdgeId::new(i)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "ProblemData<F>"))]
+pub struct MinCostFlowProblem<F: Flow> {
+    pub num_of_nodes: usize,
+    pub edges: Vec<ProblemEdge<F>>,
+    pub supply: Vec<F>,
+    pub node_names: Vec<Option<String>>,
+}
+
+impl<F: Flow> MinCostFlowProblem<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        MinCostFlowProblem { num_of_nodes, edges: Vec::new(), supply: vec![F::zero(); num_of_nodes], node_names: vec![None; num_of_nodes] }
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.num_of_nodes += 1;
+        self.supply.push(F::zero());
+        self.node_names.push(None);
+        self.num_of_nodes - 1
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        self.edges.push(ProblemEdge { from, to, lower, upper, cost, name: None });
+        EdgeId::new(self.edges.len() - 1)
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.supply[node] += supply;
+    }
+
+    pub fn set_node_name(&mut self, node: usize, name: &str) {
+        self.node_names[node] = Some(name.to_string());
+    }
+
+    pub fn set_edge_name(&mut self, edge_id: EdgeId, name: &str) {
+        self.edges[edge_id.index()].name = Some(name.to_string());
+    }
+
+    pub fn num_of_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    // add the nodes, edges and supplies to a solver
+    // the returned ids are in the same order as self.edges
+    pub fn load<S: MinCostFlowSolver<F>>(&self, solver: &mut S) -> Vec<EdgeId> {
+        let edge_ids = self.edges.iter().map(|e| solver.add_directed_edge(e.from, e.to, e.lower, e.upper, e.cost)).collect();
+        for u in 0..self.num_of_nodes {
+            if self.supply[u] != F::zero() {
+                solver.add_supply(u, self.supply[u]);
+            }
+        }
+        edge_ids
+    }
+
+    pub fn build<S: MinCostFlowSolver<F>>(&self) -> (S, Vec<EdgeId>) {
+        let mut solver = S::new(self.num_of_nodes);
+        let edge_ids = self.load(&mut solver);
+        (solver, edge_ids)
+    }
+
+    pub fn solve<S: MinCostFlowSolver<F>>(&self) -> Solution<F> {
+        let (mut solver, edge_ids) = self.build::<S>();
+        solver.solve();
+        Solution::from_solver(&solver, &edge_ids)
+    }
+
+    pub fn cost(&self, flows: &[F]) -> i128 {
+        let mut cost = 0;
+        for (e, flow) in self.edges.iter().zip(flows.iter()) {
+            cost += F::to_i128(flow).unwrap() * F::to_i128(&e.cost).unwrap();
+        }
+        cost
+    }
+
+    // checks the bounds, the flow conservation and the cost of an optimal solution
+    // if the potentials are given, they must prove the optimality (complementary slackness)
+    pub fn check_solution(&self, solution: &Solution<F>) -> Result<(), String> {
+        if solution.status != Status::Optimal {
+            return Err(format!("status is {:?}", solution.status));
+        }
+        if solution.flows.len() != self.num_of_edges() {
+            return Err(format!("{} flows for {} edges", solution.flows.len(), self.num_of_edges()));
+        }
+
+        let mut balance: Vec<i128> = self.supply.iter().map(|s| F::to_i128(s).unwrap()).collect();
+        for (i, (e, &flow)) in self.edges.iter().zip(solution.flows.iter()).enumerate() {
+            if flow < e.lower || e.upper < flow {
+                return Err(format!("flow {} of edge {} is out of [{}, {}]", flow, i, e.lower, e.upper));
+            }
+            balance[e.from] -= F::to_i128(&flow).unwrap();
+            balance[e.to] += F::to_i128(&flow).unwrap();
+        }
+        for u in 0..self.num_of_nodes {
+            if balance[u] != 0 {
+                return Err(format!("flow is not conserved at node {} (excess {})", u, balance[u]));
+            }
+        }
+
+        let cost = self.cost(&solution.flows);
+        if solution.cost != Some(cost) {
+            return Err(format!("cost is {:?} but the flows cost {}", solution.cost, cost));
+        }
+
+        if solution.potentials.is_empty() {
+            return Ok(());
+        }
+        if solution.potentials.len() != self.num_of_nodes {
+            return Err(format!("{} potentials for {} nodes", solution.potentials.len(), self.num_of_nodes));
+        }
+        let p: Vec<i128> = solution.potentials.iter().map(|p| F::to_i128(p).unwrap()).collect();
+        for (i, (e, &flow)) in self.edges.iter().zip(solution.flows.iter()).enumerate() {
+            let reduced_cost = F::to_i128(&e.cost).unwrap() + p[e.from] - p[e.to];
+            if (reduced_cost > 0 && flow != e.lower) || (reduced_cost < 0 && flow != e.upper) {
+                return Err(format!("edge {} has reduced cost {} and flow {} (bounds [{}, {}])", i, reduced_cost, flow, e.lower, e.upper));
+            }
+        }
+        Ok(())
+    }
+}
+
+// flows[i] is the flow of edge i of the problem
+// flows and potentials are empty unless status is Optimal
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Solution<F: Flow> {
+    pub status: Status,
+    pub flows: Vec<F>,
+    pub potentials: Vec<F>,
+    pub cost: Option<i128>,
+}
+
+impl<F: Flow> Solution<F> {
+    pub fn from_solver<S: MinCostFlowSolver<F>>(solver: &S, edge_ids: &[EdgeId]) -> Self {
+        let status = solver.status();
+        if status != Status::Optimal {
+            return Solution { status, flows: Vec::new(), potentials: Vec::new(), cost: None };
+        }
+
+        let flows = edge_ids.iter().map(|&edge_id| solver.get_directed_edge(edge_id).flow).collect();
+        Solution { status, flows, potentials: solver.get_potential(), cost: solver.optimal_cost() }
+    }
+}
+
+// a maximum flow instance. edges are (from, to, capacity)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "MaxFlowProblemData<F>"))]
+pub struct MaxFlowProblem<F: Flow> {
+    pub num_of_nodes: usize,
+    pub source: usize,
+    pub sink: usize,
+    pub edges: Vec<(usize, usize, F)>,
+}
+
+impl<F: Flow> MaxFlowProblem<F> {
+    pub fn new(num_of_nodes: usize, source: usize, sink: usize) -> Self {
+        assert!(source < num_of_nodes);
+        assert!(sink < num_of_nodes);
+        assert_ne!(source, sink);
+        MaxFlowProblem { num_of_nodes, source, sink, edges: Vec::new() }
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, upper: F) -> usize {
+        assert!(upper >= F::zero());
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        self.edges.push((from, to, upper));
+        self.edges.len() - 1
+    }
+
+    // the edges have cost 0, and the last edge sink -> source has cost -1
+    // so the optimal cost is -(maximum flow) and the flow of edge i is the flow of edges[i]
+    pub fn to_min_cost_flow_problem(&self) -> MinCostFlowProblem<F> {
+        let mut problem = MinCostFlowProblem::new(self.num_of_nodes);
+        let mut total = 0;
+        for &(from, to, upper) in self.edges.iter() {
+            problem.add_directed_edge(from, to, F::zero(), upper, F::zero());
+            if from == self.source && to != self.source {
+                total += F::to_i128(&upper).unwrap();
+            }
+        }
+        let total = F::from_i128(i128::min(total, F::to_i128(&F::max_value()).unwrap())).unwrap();
+        problem.add_directed_edge(self.sink, self.source, F::zero(), total, -F::one());
+        problem
+    }
+}
+
+// the unchecked form of the problems for deserialization
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ProblemData<F: Flow> {
+    num_of_nodes: usize,
+    edges: Vec<ProblemEdge<F>>,
+    #[serde(default = "Vec::new")]
+    supply: Vec<F>,
+    #[serde(default = "Vec::new")]
+    node_names: Vec<Option<String>>,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Flow> TryFrom<ProblemData<F>> for MinCostFlowProblem<F> {
+    type Error = String;
+
+    fn try_from(data: ProblemData<F>) -> Result<Self, String> {
+        let n = data.num_of_nodes;
+        for (i, e) in data.edges.iter().enumerate() {
+            if e.from >= n || e.to >= n {
+                return Err(format!("edge {} ({} -> {}) has a node out of range [0, {})", i, e.from, e.to, n));
+            }
+            if e.lower > e.upper {
+                return Err(format!("edge {} has lower bound {} greater than upper bound {}", i, e.lower, e.upper));
+            }
+        }
+        if !data.supply.is_empty() && data.supply.len() != n {
+            return Err(format!("{} supplies for {} nodes", data.supply.len(), n));
+        }
+        if !data.node_names.is_empty() && data.node_names.len() != n {
+            return Err(format!("{} node names for {} nodes", data.node_names.len(), n));
+        }
+
+        let mut problem = MinCostFlowProblem::new(n);
+        problem.edges = data.edges;
+        if !data.supply.is_empty() {
+            problem.supply = data.supply;
+        }
+        if !data.node_names.is_empty() {
+            problem.node_names = data.node_names;
+        }
+        Ok(problem)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct MaxFlowProblemData<F: Flow> {
+    num_of_nodes: usize,
+    source: usize,
+    sink: usize,
+    edges: Vec<(usize, usize, F)>,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Flow> TryFrom<MaxFlowProblemData<F>> for MaxFlowProblem<F> {
+    type Error = String;
+
+    fn try_from(data: MaxFlowProblemData<F>) -> Result<Self, String> {
+        let n = data.num_of_nodes;
+        if data.source >= n || data.sink >= n || data.source == data.sink {
+            return Err(format!("invalid source {} and sink {} for {} nodes", data.source, data.sink, n));
+        }
+        for (i, &(from, to, upper)) in data.edges.iter().enumerate() {
+            if from >= n || to >= n {
+                return Err(format!("edge {} ({} -> {}) has a node out of range [0, {})", i, from, to, n));
+            }
+            if upper < F::zero() {
+                return Err(format!("edge {} has negative capacity {}", i, upper));
+            }
+        }
+        Ok(MaxFlowProblem { num_of_nodes: n, source: data.source, sink: data.sink, edges: data.edges })
+    }
+}
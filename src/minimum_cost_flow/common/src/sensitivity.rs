@@ -0,0 +1,120 @@
+use crate::{Flow, MinCostFlowProblem, Solution, Status};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// the costs of an edge for which the flow of a solution stays optimal, the other costs unchanged
+// None is unbounded. the current cost is always in the range
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CostRange {
+    pub lower: Option<i128>,
+    pub upper: Option<i128>,
+}
+
+impl CostRange {
+    pub fn contains(&self, cost: i128) -> bool {
+        self.lower.is_none_or(|lower| lower <= cost) && self.upper.is_none_or(|upper| cost <= upper)
+    }
+}
+
+// residual network of a solution. arcs are (to, reduced cost, edge index)
+pub(crate) struct ResidualNetwork {
+    pub(crate) graph: Vec<Vec<(usize, i128, usize)>>,
+}
+
+impl ResidualNetwork {
+    pub(crate) fn new<F: Flow>(problem: &MinCostFlowProblem<F>, flows: &[F], potentials: &[i128]) -> Self {
+        let mut graph = vec![Vec::new(); problem.num_of_nodes];
+        for (i, (e, &flow)) in problem.edges.iter().zip(flows.iter()).enumerate() {
+            let reduced_cost = F::to_i128(&e.cost).unwrap() + potentials[e.from] - potentials[e.to];
+            if flow < e.upper {
+                graph[e.from].push((e.to, reduced_cost, i));
+            }
+            if e.lower < flow {
+                graph[e.to].push((e.from, -reduced_cost, i));
+            }
+        }
+        ResidualNetwork { graph }
+    }
+
+    // dijkstra from s without the arcs of the edge `excluded`. the reduced costs must be non-negative
+    pub(crate) fn distances(&self, s: usize, excluded: Option<usize>) -> Vec<Option<i128>> {
+        let mut distance = vec![None; self.graph.len()];
+        let mut heap = BinaryHeap::new();
+        distance[s] = Some(0);
+        heap.push(Reverse((0, s)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if distance[u] != Some(d) {
+                continue;
+            }
+            for &(v, reduced_cost, i) in self.graph[u].iter() {
+                if Some(i) == excluded {
+                    continue;
+                }
+                let new_distance = d + reduced_cost;
+                if distance[v].is_none_or(|old| new_distance < old) {
+                    distance[v] = Some(new_distance);
+                    heap.push(Reverse((new_distance, v)));
+                }
+            }
+        }
+        distance
+    }
+}
+
+impl<F: Flow> MinCostFlowProblem<F> {
+    // cost ranging: ranges[i] is the range of the cost of edge i (EdgeId::new(i)) for which solution stays optimal
+    // the flow stays optimal as long as the residual network has no negative cycle. a cycle through edge (a, b)
+    // closes with a shortest path b -> a (or a -> b for the reverse arc), so with the shortest path distances d
+    //     flow < upper: cost >= -d(b, a)
+    //     lower < flow: cost <= d(a, b)
+    // the potentials of the solution make the reduced costs non-negative, so the distances are found by dijkstra.
+    // a simple path b -> a can use an arc of the edge only if it is the reverse arc itself, so the arcs of the
+    // edge are excluded only for the edges with lower < flow < upper, the others share one dijkstra per node
+    pub fn cost_ranges(&self, solution: &Solution<F>) -> Vec<CostRange> {
+        assert_eq!(solution.status, Status::Optimal);
+        assert_eq!(solution.flows.len(), self.num_of_edges());
+        assert_eq!(solution.potentials.len(), self.num_of_nodes);
+
+        let p: Vec<i128> = solution.potentials.iter().map(|p| F::to_i128(p).unwrap()).collect();
+        let network = ResidualNetwork::new(self, &solution.flows, &p);
+        // the cost of a path u -> v is its reduced cost minus p[u] plus p[v]
+        let cost = |d: Option<i128>, u: usize, v: usize| d.map(|d| d - p[u] + p[v]);
+
+        let mut ranges = vec![CostRange { lower: None, upper: None }; self.num_of_edges()];
+        let mut sources = vec![Vec::new(); self.num_of_nodes];
+        for (i, (e, &flow)) in self.edges.iter().zip(solution.flows.iter()).enumerate() {
+            let (a, b) = (e.from, e.to);
+            if a == b {
+                // the edge alone is a cycle
+                ranges[i] = CostRange { lower: if flow < e.upper { Some(0) } else { None }, upper: if e.lower < flow { Some(0) } else { None } };
+            } else if e.lower < flow && flow < e.upper {
+                let lower = cost(network.distances(b, Some(i))[a], b, a).map(|d| -d);
+                let upper = cost(network.distances(a, Some(i))[b], a, b);
+                ranges[i] = CostRange { lower, upper };
+            } else if flow < e.upper {
+                sources[b].push(i);
+            } else if e.lower < flow {
+                sources[a].push(i);
+            }
+        }
+
+        for s in 0..self.num_of_nodes {
+            if sources[s].is_empty() {
+                continue;
+            }
+            let distance = network.distances(s, None);
+            for &i in sources[s].iter() {
+                let (a, b) = (self.edges[i].from, self.edges[i].to);
+                if s == b {
+                    ranges[i].lower = cost(distance[a], b, a).map(|d| -d);
+                } else {
+                    ranges[i].upper = cost(distance[b], a, b);
+                }
+            }
+        }
+        ranges
+    }
+}
@@ -0,0 +1,108 @@
+use minimum_cost_flow_common::{decompose_flow, Edge, EdgeId, FlowPath};
+use test_support::Random;
+
+fn edge(from: usize, to: usize, flow: i64, cost: i64) -> Edge<i64> {
+    Edge::new(from, to, flow, i64::min(flow, 0), i64::max(flow, 0), cost)
+}
+
+fn path(nodes: Vec<usize>, edges: Vec<usize>, amount: i64, cost: i128) -> FlowPath<i64> {
+    FlowPath { nodes, edges: edges.into_iter().map(EdgeId::new).collect(), amount, cost }
+}
+
+// the supplies that the flows satisfy
+fn supply_of(edges: &[Edge<i64>], n: usize) -> Vec<i64> {
+    let mut supply = vec![0; n];
+    for e in edges.iter() {
+        supply[e.from] += e.flow;
+        supply[e.to] -= e.flow;
+    }
+    supply
+}
+
+// the paths and cycles follow the flows, use up the supplies and add up to the flows and their cost
+fn assert_recomposes(edges: &[Edge<i64>], supply: &[i64], paths: &[FlowPath<i64>]) {
+    let mut flows = vec![0; edges.len()];
+    let mut balance = supply.to_vec();
+    let mut cost = 0;
+    for path in paths.iter() {
+        assert!(path.amount > 0, "{:?}", path);
+        assert_eq!(path.nodes.len(), path.edges.len() + 1, "{:?}", path);
+        let mut unit_cost = 0;
+        for (k, i) in path.edges.iter().enumerate() {
+            let e = &edges[i.index()];
+            if e.flow > 0 {
+                assert_eq!((e.from, e.to), (path.nodes[k], path.nodes[k + 1]), "{:?}", path);
+                flows[i.index()] += path.amount;
+                unit_cost += e.cost as i128;
+            } else {
+                assert_eq!((e.to, e.from), (path.nodes[k], path.nodes[k + 1]), "{:?}", path);
+                flows[i.index()] -= path.amount;
+                unit_cost -= e.cost as i128;
+            }
+        }
+        assert_eq!(path.cost, unit_cost * path.amount as i128, "{:?}", path);
+        cost += path.cost;
+
+        if !path.is_cycle() {
+            let (s, t) = (path.nodes[0], *path.nodes.last().unwrap());
+            balance[s] -= path.amount;
+            balance[t] += path.amount;
+            assert!(balance[s] >= 0 && balance[t] <= 0, "{:?}", path);
+        }
+    }
+    assert_eq!(flows, edges.iter().map(|e| e.flow).collect::<Vec<_>>());
+    assert!(balance.iter().all(|&b| b == 0));
+    assert_eq!(cost, edges.iter().map(|e| e.flow as i128 * e.cost as i128).sum::<i128>());
+    assert!(paths.len() <= edges.iter().filter(|e| e.flow != 0).count());
+}
+
+#[test]
+fn decomposes_flow() {
+    // 3 units from 0 to 2, 1 of them backwards through 2 -> 0, a self loop and a cycle 1 -> 3 -> 1
+    let edges = vec![edge(0, 1, 2, 1), edge(1, 2, 2, 2), edge(2, 0, -1, 4), edge(1, 1, 2, -1), edge(1, 3, 1, 1), edge(3, 1, 1, 1), edge(0, 3, 0, 5)];
+    let supply = vec![3, 0, -3, 0];
+    let paths = decompose_flow(&edges, &supply);
+
+    // the self loops, then the paths from the nodes with supply, then the remaining cycles
+    let expected = vec![path(vec![1, 1], vec![3], 2, -2), path(vec![0, 1, 2], vec![0, 1], 2, 6), path(vec![0, 2], vec![2], 1, -4), path(vec![1, 3, 1], vec![4, 5], 1, 2)];
+    assert_eq!(paths, expected);
+    assert_recomposes(&edges, &supply, &paths);
+    assert_eq!(paths.iter().map(|path| path.cost).sum::<i128>(), 2);
+}
+
+#[test]
+fn removes_cycles_on_the_way() {
+    // the walk from 0 goes around 1 -> 2 -> 3 -> 1 before it leaves 1 for 4
+    let edges = vec![edge(0, 1, 1, 0), edge(1, 2, 2, 1), edge(2, 3, 2, 1), edge(3, 1, 2, 1), edge(1, 4, 1, 0)];
+    let supply = vec![1, 0, 0, 0, -1];
+    let paths = decompose_flow(&edges, &supply);
+    assert_eq!(paths, vec![path(vec![1, 2, 3, 1], vec![1, 2, 3], 2, 6), path(vec![0, 1, 4], vec![0, 4], 1, 0)]);
+    assert_recomposes(&edges, &supply, &paths);
+}
+
+#[test]
+fn decomposes_random_flows() {
+    let mut random = Random(0x9e3779b97f4a7c15);
+    for case in 0..20000 {
+        let n = random.next(1, 6) as usize;
+        let m = random.next(0, 12) as usize;
+        let edges: Vec<Edge<i64>> = (0..m)
+            .map(|_| {
+                let from = random.next(0, n as i64 - 1) as usize;
+                let to = random.next(0, n as i64 - 1) as usize;
+                edge(from, to, random.next(-3, 3), random.next(-5, 5))
+            })
+            .collect();
+        let supply = supply_of(&edges, n);
+
+        let paths = decompose_flow(&edges, &supply);
+        assert_recomposes(&edges, &supply, &paths);
+        assert_eq!(decompose_flow(&edges, &supply), paths, "case {}", case);
+    }
+}
+
+#[test]
+#[should_panic(expected = "the flow does not satisfy the supplies")]
+fn rejects_unbalanced_flow() {
+    decompose_flow(&[edge(0, 1, 1, 0)], &[0, 0]);
+}
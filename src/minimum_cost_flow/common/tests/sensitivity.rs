@@ -0,0 +1,87 @@
+use minimum_cost_flow_common::{CostRange, MinCostFlowProblem, Solution, Status};
+use test_support::brute_force;
+
+// 5 units from 0 to 3: 1 on the fixed edge 0 -> 3, 3 on 0 -> 1 -> 3 (cost 2) and 1 on 0 -> 2 -> 3 (cost 4)
+fn solved() -> (MinCostFlowProblem<i64>, Solution<i64>) {
+    let mut problem = MinCostFlowProblem::new(4);
+    problem.add_directed_edge(0, 1, 0, 3, 1); // at upper
+    problem.add_directed_edge(1, 3, 0, 4, 1); // interior
+    problem.add_directed_edge(0, 2, 0, 4, 3); // interior
+    problem.add_directed_edge(2, 3, 0, 2, 1); // interior
+    problem.add_directed_edge(1, 2, 0, 5, 3); // at lower
+    problem.add_directed_edge(0, 3, 1, 1, 10); // fixed
+    problem.add_directed_edge(1, 1, 0, 2, -1); // self loop at upper
+    problem.add_directed_edge(3, 3, 0, 5, 2); // self loop at lower
+    problem.add_directed_edge(2, 2, -1, 1, 0); // self loop in the interior
+    problem.add_supply(0, 5);
+    problem.add_supply(3, -5);
+
+    let flows = vec![3, 3, 1, 1, 0, 1, 2, 0, 0];
+    let solution = Solution { status: Status::Optimal, cost: Some(problem.cost(&flows)), flows, potentials: vec![-4, -1, -1, 0] };
+    problem.check_solution(&solution).unwrap();
+    assert_eq!(solution.cost, brute_force(&problem).1);
+    (problem, solution)
+}
+
+fn range(lower: Option<i128>, upper: Option<i128>) -> CostRange {
+    CostRange { lower, upper }
+}
+
+#[test]
+fn cost_ranges() {
+    let (problem, solution) = solved();
+    let expected = vec![
+        // 0 -> 2 -> 3 -> 1 costs 3
+        range(None, Some(3)),
+        // 1 -> 0 -> 2 -> 3 costs 3, and nothing goes from 3 back to 1 without it
+        range(None, Some(3)),
+        // 2 -> 3 -> 1 -> 0 costs -1, and the flow out of 0 that the other edges can not take goes through it
+        range(Some(1), None),
+        // 3 -> 1 -> 0 -> 2 costs 1
+        range(Some(-1), None),
+        // 2 -> 3 -> 1 costs 0
+        range(Some(0), None),
+        range(None, None),
+        range(None, Some(0)),
+        range(Some(0), None),
+        range(Some(0), Some(0)),
+    ];
+    assert_eq!(problem.cost_ranges(&solution), expected);
+}
+
+#[test]
+fn flow_changes_outside_the_ranges() {
+    let (problem, solution) = solved();
+    let ranges = problem.cost_ranges(&solution);
+    for (i, range) in ranges.iter().enumerate() {
+        let cost = problem.edges[i].cost as i128;
+        assert!(range.contains(cost));
+
+        // the flow stays optimal at the ends of the range, and some other flow is cheaper just outside it
+        let mut ends = vec![];
+        if let Some(lower) = range.lower {
+            ends.push((lower, lower - 1));
+        }
+        if let Some(upper) = range.upper {
+            ends.push((upper, upper + 1));
+        }
+        for &(inside, outside) in ends.iter() {
+            let mut perturbed = problem.clone();
+            perturbed.edges[i].cost = inside as i64;
+            assert_eq!(brute_force(&perturbed).1, Some(perturbed.cost(&solution.flows)), "edge {} cost {}", i, inside);
+            perturbed.edges[i].cost = outside as i64;
+            assert!(brute_force(&perturbed).1.unwrap() < perturbed.cost(&solution.flows), "edge {} cost {}", i, outside);
+        }
+
+        // an unbounded side stays optimal however far the cost goes
+        let mut perturbed = problem.clone();
+        if range.lower.is_none() {
+            perturbed.edges[i].cost = -1000;
+            assert_eq!(brute_force(&perturbed).1, Some(perturbed.cost(&solution.flows)), "edge {} cost -1000", i);
+        }
+        if range.upper.is_none() {
+            perturbed.edges[i].cost = 1000;
+            assert_eq!(brute_force(&perturbed).1, Some(perturbed.cost(&solution.flows)), "edge {} cost 1000", i);
+        }
+    }
+}
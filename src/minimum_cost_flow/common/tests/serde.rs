@@ -0,0 +1,114 @@
+#![cfg(feature = "serde")]
+use minimum_cost_flow_common::{CostRange, Edge, EdgeId, FlowPath, MaxFlowProblem, MinCostFlowProblem, ProblemEdge, Solution, Status};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+
+// x survives JSON and bincode, and its JSON is expected
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(x: &T, expected: Value) {
+    let text = serde_json::to_string(x).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&text).unwrap(), expected);
+    assert_eq!(&serde_json::from_str::<T>(&text).unwrap(), x);
+
+    let bytes = bincode::serialize(x).unwrap();
+    assert_eq!(&bincode::deserialize::<T>(&bytes).unwrap(), x);
+}
+
+fn problem() -> MinCostFlowProblem<i64> {
+    let mut problem = MinCostFlowProblem::new(2);
+    let e = problem.add_directed_edge(0, 1, 0, 5, 3);
+    problem.set_edge_name(e, "e0");
+    problem.set_node_name(0, "s");
+    problem.add_supply(0, 5);
+    problem.add_supply(1, -5);
+    problem
+}
+
+// the examples of the schema in lib.rs
+#[test]
+fn follows_the_schema() {
+    let expected = json!({"num_of_nodes": 2, "edges": [{"from": 0, "to": 1, "lower": 0, "upper": 5, "cost": 3, "name": "e0"}], "supply": [5, -5], "node_names": ["s", null]});
+    assert_round_trip(&problem(), expected);
+
+    let mut max_flow = MaxFlowProblem::new(2, 0, 1);
+    max_flow.add_edge(0, 1, 5);
+    assert_round_trip(&max_flow, json!({"num_of_nodes": 2, "source": 0, "sink": 1, "edges": [[0, 1, 5]]}));
+
+    assert_round_trip(&CostRange { lower: Some(-3), upper: None }, json!({"lower": -3, "upper": null}));
+    let solution: Solution<i64> = Solution { status: Status::Optimal, flows: vec![5], potentials: vec![0, 3], cost: Some(15) };
+    assert_round_trip(&solution, json!({"status": "Optimal", "flows": [5], "potentials": [0, 3], "cost": 15}));
+    let path: FlowPath<i64> = FlowPath { nodes: vec![0, 1, 2], edges: vec![EdgeId::new(0), EdgeId::new(3)], amount: 2, cost: 8 };
+    assert_round_trip(&path, json!({"nodes": [0, 1, 2], "edges": [0, 3], "amount": 2, "cost": 8}));
+    assert_round_trip(&Edge::<i64>::new(0, 1, 5, 0, 5, 3), json!({"from": 0, "to": 1, "flow": 5, "lower": 0, "upper": 5, "cost": 3}));
+    assert_round_trip(&EdgeId::new(0), json!(0));
+    assert_round_trip(&Status::Infeasible, json!("Infeasible"));
+}
+
+#[test]
+fn round_trips() {
+    // every status, and values that need i128
+    for &status in [Status::NotSolved, Status::Optimal, Status::Feasible, Status::Infeasible, Status::Unbalanced, Status::BadResult, Status::BadCostRange].iter() {
+        assert_round_trip(&status, json!(format!("{:?}", status)));
+    }
+    let solution: Solution<i128> = Solution { status: Status::Optimal, flows: vec![i128::MIN, i128::MAX], potentials: vec![], cost: Some(i128::MAX) };
+    let text = serde_json::to_string(&solution).unwrap();
+    assert_eq!(serde_json::from_str::<Solution<i128>>(&text).unwrap(), solution);
+    assert_eq!(bincode::deserialize::<Solution<i128>>(&bincode::serialize(&solution).unwrap()).unwrap(), solution);
+
+    let edge = ProblemEdge { from: 1, to: 1, lower: -2, upper: 4, cost: -1, name: None };
+    assert_round_trip(&edge, json!({"from": 1, "to": 1, "lower": -2, "upper": 4, "cost": -1, "name": null}));
+    let mut problem = problem();
+    problem.edges.push(edge);
+    let text = serde_json::to_string(&problem).unwrap();
+    assert_eq!(serde_json::from_str::<MinCostFlowProblem<i64>>(&text).unwrap(), problem);
+    assert_eq!(bincode::deserialize::<MinCostFlowProblem<i64>>(&bincode::serialize(&problem).unwrap()).unwrap(), problem);
+
+    assert_round_trip(&CostRange { lower: None, upper: Some(i64::MIN as i128) }, json!({"lower": null, "upper": i64::MIN}));
+    let self_loop: FlowPath<i32> = FlowPath { nodes: vec![4, 4], edges: vec![EdgeId::new(7)], amount: 1, cost: -1 };
+    assert_round_trip(&self_loop, json!({"nodes": [4, 4], "edges": [7], "amount": 1, "cost": -1}));
+}
+
+#[test]
+fn fills_omitted_fields() {
+    let problem: MinCostFlowProblem<i64> = serde_json::from_value(json!({"num_of_nodes": 2, "edges": [{"from": 0, "to": 1, "lower": 0, "upper": 5, "cost": 3}]})).unwrap();
+    let mut expected = MinCostFlowProblem::new(2);
+    expected.add_directed_edge(0, 1, 0, 5, 3);
+    assert_eq!(problem, expected);
+}
+
+// the message of the error of deserializing value
+fn error<T: DeserializeOwned + Debug>(value: Value) -> String {
+    serde_json::from_value::<T>(value).unwrap_err().to_string()
+}
+
+#[test]
+fn validates_problems() {
+    let edge = |from: usize, to: usize, lower: i64, upper: i64| json!({"from": from, "to": to, "lower": lower, "upper": upper, "cost": 0});
+    let cases = [
+        (json!({"num_of_nodes": 2, "edges": [edge(0, 1, 0, 1), edge(0, 2, 0, 1)]}), "edge 1 (0 -> 2) has a node out of range [0, 2)"),
+        (json!({"num_of_nodes": 2, "edges": [edge(2, 0, 0, 1)]}), "edge 0 (2 -> 0) has a node out of range [0, 2)"),
+        (json!({"num_of_nodes": 2, "edges": [edge(0, 1, 2, 1)]}), "edge 0 has lower bound 2 greater than upper bound 1"),
+        (json!({"num_of_nodes": 2, "edges": [], "supply": [1, 0, -1]}), "3 supplies for 2 nodes"),
+        (json!({"num_of_nodes": 2, "edges": [], "node_names": ["a"]}), "1 node names for 2 nodes"),
+    ];
+    for (value, message) in cases.iter() {
+        assert_eq!(error::<MinCostFlowProblem<i64>>(value.clone()), *message);
+    }
+
+    let cases = [
+        (json!({"num_of_nodes": 2, "source": 0, "sink": 2, "edges": []}), "invalid source 0 and sink 2 for 2 nodes"),
+        (json!({"num_of_nodes": 2, "source": 1, "sink": 1, "edges": []}), "invalid source 1 and sink 1 for 2 nodes"),
+        (json!({"num_of_nodes": 2, "source": 0, "sink": 1, "edges": [[0, 1, 1], [1, 3, 1]]}), "edge 1 (1 -> 3) has a node out of range [0, 2)"),
+        (json!({"num_of_nodes": 2, "source": 0, "sink": 1, "edges": [[0, 1, -1]]}), "edge 0 has negative capacity -1"),
+    ];
+    for (value, message) in cases.iter() {
+        assert_eq!(error::<MaxFlowProblem<i64>>(value.clone()), *message);
+    }
+
+    // bincode checks too
+    let mut problem = problem();
+    problem.edges[0].lower = 6;
+    let bytes = bincode::serialize(&problem).unwrap();
+    assert_eq!(bincode::deserialize::<MinCostFlowProblem<i64>>(&bytes).unwrap_err().to_string(), "edge 0 has lower bound 6 greater than upper bound 5");
+}
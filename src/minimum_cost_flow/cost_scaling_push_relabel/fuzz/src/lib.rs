@@ -0,0 +1,203 @@
+use arbitrary::{Arbitrary, Unstructured};
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use minimum_cost_flow_common::{EdgeId, MinCostFlowProblem, MinCostFlowSolver, Solution, Status};
+use push_relabel::LowerBound;
+
+// the fuzz targets build small networks with self loops, parallel edges, negative lower bounds and costs,
+// capacities up to i32::MAX and costs near the extremes of i32. large costs and capacities give BadCostRange
+// the supplies and the finite bounds are at most LIMIT in absolute value
+
+const LIMIT: i32 = i32::MAX / 256;
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+enum Value {
+    Small(i8),
+    Max,
+    Min,
+    Any(i32),
+}
+
+impl Value {
+    fn finite(self) -> i32 {
+        match self {
+            Value::Small(x) => x as i32,
+            Value::Max => LIMIT,
+            Value::Min => -LIMIT,
+            Value::Any(x) => x.clamp(-LIMIT, LIMIT),
+        }
+    }
+
+    fn capacity(self) -> i32 {
+        match self {
+            Value::Max => i32::MAX,
+            _ => self.finite(),
+        }
+    }
+
+    // the solver negates the costs
+    fn cost(self) -> i32 {
+        match self {
+            Value::Small(x) => x as i32,
+            Value::Max => i32::MAX,
+            Value::Min => -i32::MAX,
+            Value::Any(x) => x.max(-i32::MAX),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+struct ArbitraryEdge {
+    from: u8,
+    to: u8,
+    lower: Value,
+    upper: Value,
+    cost: Value,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Network {
+    num_of_nodes: u8,
+    edges: Vec<ArbitraryEdge>,
+    supplies: Vec<(u8, Value)>,
+    heuristics: u8,
+}
+
+const MAX_NODES: usize = 16;
+const MAX_EDGES: usize = 64;
+
+impl Network {
+    // lower and upper are swapped if needed, and lower is at least 0 with capacity i32::MAX
+    fn problem(&self) -> MinCostFlowProblem<i32> {
+        let n = self.num_of_nodes as usize % MAX_NODES + 1;
+        let mut problem = MinCostFlowProblem::new(n);
+        for &(u, supply) in self.supplies.iter().take(MAX_NODES) {
+            let u = u as usize % n;
+            problem.supply[u] = supply.finite();
+        }
+
+        for e in self.edges.iter().take(MAX_EDGES) {
+            let (a, b) = (e.lower.finite(), e.upper.capacity());
+            let (mut lower, upper) = (a.min(b), a.max(b));
+            if upper == i32::MAX {
+                // upper - lower must fit in i32
+                lower = lower.max(0);
+            }
+            problem.add_directed_edge(e.from as usize % n, e.to as usize % n, lower, upper, e.cost.cost());
+        }
+        problem
+    }
+
+    // bit 0: look-ahead, bit 1: price update, bit 2: price refinement, bit 3: alpha = 2 instead of 5
+    // the feasibility check stays on, without it infeasible problems are not detected
+    fn solver(&self, problem: &MinCostFlowProblem<i32>) -> (CostScalingPushRelabel<i32>, Vec<EdgeId>) {
+        let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i32>>();
+        solver.use_look_ahead_heuristic(self.heuristics & 1 != 0);
+        solver.use_price_update_heuristic(self.heuristics & 2 != 0);
+        solver.use_price_refinement_heuristic(self.heuristics & 4 != 0);
+        solver.set_alpha(self.alpha() as i32);
+        (solver, edge_ids)
+    }
+
+    fn alpha(&self) -> i64 {
+        if self.heuristics & 8 != 0 {
+            2
+        } else {
+            5
+        }
+    }
+}
+
+// the supplies plus 2 * |lower| + |upper| of the edges other than self loops, which bounds the excesses
+fn load(problem: &MinCostFlowProblem<i32>) -> i64 {
+    let supplies: i64 = problem.supply.iter().map(|&s| (s as i64).abs()).sum();
+    let flows: i64 = problem.edges.iter().filter(|e| e.from != e.to).map(|e| 2 * (e.lower as i64).abs() + (e.upper as i64).abs()).sum();
+    supplies + flows
+}
+
+// the status the solver must return, checked in the order of solve
+fn expected_status(problem: &MinCostFlowProblem<i32>, alpha: i64) -> Status {
+    let n = problem.num_of_nodes as i64;
+    let gamma = problem.edges.iter().map(|e| (e.cost as i64).abs()).max().unwrap_or(0);
+    if load(problem) > i32::MAX as i64 {
+        return Status::BadCostRange;
+    }
+    if problem.supply.iter().map(|&s| s as i64).sum::<i64>() != 0 {
+        return Status::Unbalanced;
+    }
+
+    let mut solver = LowerBound::new(problem.num_of_nodes);
+    for e in problem.edges.iter().filter(|e| e.from != e.to) {
+        solver.add_edge(e.from, e.to, e.lower, e.upper);
+    }
+    for u in 0..problem.num_of_nodes {
+        solver.add_supply(u, problem.supply[u]);
+    }
+    if solver.solve().is_none() {
+        return Status::Infeasible;
+    }
+
+    // the scaled costs and the reduced costs must fit
+    if gamma as i128 * (alpha * n) as i128 * (12 * n + 2) as i128 > i32::MAX as i128 {
+        return Status::BadCostRange;
+    }
+    Status::Optimal
+}
+
+// an optimal solution must be a valid flow with the reported cost, proven optimal by the potentials
+fn check(problem: &MinCostFlowProblem<i32>, solver: &CostScalingPushRelabel<i32>, edge_ids: &[EdgeId], alpha: i64) {
+    let solution = Solution::from_solver(solver, edge_ids);
+    assert_eq!(solution.status, expected_status(problem, alpha), "{:?}", problem);
+    if solution.status == Status::Optimal {
+        if let Err(e) = problem.check_solution(&solution) {
+            panic!("invalid solution: {}\n{:?}\n{:?}", e, problem, solution);
+        }
+    }
+}
+
+pub fn solve(data: &[u8]) {
+    let network = match Network::arbitrary(&mut Unstructured::new(data)) {
+        Ok(network) => network,
+        Err(_) => return,
+    };
+    let problem = network.problem();
+    let (mut solver, edge_ids) = network.solver(&problem);
+    MinCostFlowSolver::solve(&mut solver);
+    check(&problem, &solver, &edge_ids, network.alpha());
+}
+
+// solves, then increases the capacities of edges one unit at a time and checks that the flow stays optimal
+pub fn increase_capacity(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let network = match Network::arbitrary(&mut u) {
+        Ok(network) => network,
+        Err(_) => return,
+    };
+    let mut problem = network.problem();
+    let (mut solver, edge_ids) = network.solver(&problem);
+    if MinCostFlowSolver::solve(&mut solver) != Status::Optimal || edge_ids.is_empty() {
+        return;
+    }
+
+    // arbitrary returns zeros when the data runs out
+    while !u.is_empty() {
+        let k = u.arbitrary::<u8>().unwrap();
+        let edge_id = edge_ids[k as usize % edge_ids.len()];
+        // the excesses of the reference solve must still fit
+        let mut increased = problem.clone();
+        let e = &mut increased.edges[edge_id.index()];
+        e.upper = e.upper.saturating_add(1);
+        if load(&increased) > i32::MAX as i64 {
+            continue;
+        }
+        solver.increase_capacity_unit(edge_id);
+        solver.update_potential();
+        problem = increased;
+        check(&problem, &solver, &edge_ids, network.alpha());
+
+        // the cost must be the optimal cost of the new problem
+        let (mut reference, _) = network.solver(&problem);
+        MinCostFlowSolver::solve(&mut reference);
+        assert_eq!(reference.status(), Status::Optimal);
+        assert_eq!(reference.optimal_cost(), solver.optimal_cost(), "{:?}", problem);
+    }
+}
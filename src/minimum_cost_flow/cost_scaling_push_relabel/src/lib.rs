@@ -1,94 +1,12 @@
-use num::{CheckedMul, FromPrimitive, ToPrimitive};
-use num_traits::NumAssign;
+#![allow(clippy::needless_range_loop)]
+use minimum_cost_flow_common::decompose_flow;
 use push_relabel::LowerBound;
 use std::collections::VecDeque;
-use std::fmt::{Debug, Display};
-use std::time::Instant;
 
-pub trait Flow: 'static + Copy + Ord + Display + Debug + BoundedBelow + BoundedAbove + FromPrimitive + ToPrimitive + NumAssign + CheckedMul {}
+pub use minimum_cost_flow_common::{Edge, EdgeId, Flow, FlowPath, MinCostFlowSolver, Status};
 
-pub trait Zero {
-    fn zero() -> Self;
-}
-
-pub trait One {
-    fn one() -> Self;
-}
-
-pub trait BoundedBelow {
-    fn min_value() -> Self;
-}
-
-pub trait BoundedAbove {
-    fn max_value() -> Self;
-}
-
-macro_rules! impl_integral {
-    ($($ty:ty),*) => {
-        $(
-            impl Zero for $ty {
-                #[inline]
-                fn zero() -> Self {
-                    0
-                }
-            }
-
-            impl One for $ty {
-                #[inline]
-                fn one() -> Self {
-                    1
-                }
-            }
-
-            impl BoundedBelow for $ty {
-                #[inline]
-                fn min_value() -> Self {
-                    Self::min_value()
-                }
-            }
-
-            impl BoundedAbove for $ty {
-                #[inline]
-                fn max_value() -> Self {
-                    Self::max_value()
-                }
-            }
-
-            impl Flow for $ty {}
-        )*
-    };
-}
-
-impl_integral!(i8, i16, i32, i64, i128);
-
-#[derive(PartialEq, Debug)]
-pub enum Status {
-    NotSolved,
-    Optimal,
-    Feasible,
-    Infeasible,
-    Unbalanced,
-    BadResult,
-    BadCostRange,
-}
-
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
-pub struct EdgeId(usize, usize);
-
-pub struct Edge<F: Flow> {
-    pub from: usize,
-    pub to: usize,
-    pub flow: F,
-    pub lower: F,
-    pub upper: F,
-    pub cost: F,
-}
-
-impl<F: Flow> Edge<F> {
-    pub fn new(from: usize, to: usize, flow: F, lower: F, upper: F, cost: F) -> Self {
-        Edge { from, to, flow, lower, upper, cost }
-    }
-}
+mod parametric;
+pub use parametric::Breakpoint;
 
 #[derive(Clone)]
 struct InternalEdge<F: Flow> {
@@ -110,9 +28,11 @@ impl<F: Flow> InternalEdge<F> {
     }
 }
 
+#[derive(Clone)]
 pub struct CostScalingPushRelabel<F: Flow> {
     num_of_nodes: usize,
     graph: Vec<Vec<InternalEdge<F>>>,
+    edges: Vec<(usize, usize)>, // graph[u][i] of each edge in the order of addition
     active_nodes: VecDeque<usize>,
     gamma: F,                  // maximum absolute value of any edge cost
     current_edges: Vec<usize>, // current candidate to test for admissibility
@@ -140,11 +60,12 @@ pub struct CostScalingPushRelabel<F: Flow> {
 }
 
 #[allow(dead_code)]
-impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
+impl<F: Flow> CostScalingPushRelabel<F> {
     pub fn new(num_of_nodes: usize) -> Self {
         CostScalingPushRelabel {
-            num_of_nodes: num_of_nodes,
+            num_of_nodes,
             graph: vec![vec![]; num_of_nodes],
+            edges: Vec::new(),
             active_nodes: VecDeque::new(),
             gamma: F::zero(),
             current_edges: vec![0; num_of_nodes],
@@ -174,6 +95,9 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         assert!(lower <= upper);
         assert!(from < self.num_of_nodes);
         assert!(to < self.num_of_nodes);
+        // the reverse edge has -lower and -cost, and the residual capacities are at most upper - lower
+        assert!(lower != F::min_value() && cost != F::min_value(), "lower and cost must be greater than F::min_value()");
+        assert!(lower >= F::zero() || upper <= F::max_value() + lower, "upper - lower must be at most F::max_value()");
 
         let e = self.graph[from].len();
         let re = if from == to { e + 1 } else { self.graph[to].len() };
@@ -192,12 +116,14 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
             self.gamma = F::max(self.gamma, cost);
         }
 
-        EdgeId(from, e)
+        self.edges.push((from, e));
+        EdgeId::new(self.edges.len() - 1)
     }
 
     pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
-        let e = &self.graph[edge_id.0][edge_id.1];
-        Edge { from: edge_id.0, to: e.to, flow: e.flow, lower: e.lower, upper: e.upper, cost: e.cost }
+        let (u, i) = self.edges[edge_id.index()];
+        let e = &self.graph[u][i];
+        Edge { from: u, to: e.to, flow: e.flow, lower: e.lower, upper: e.upper, cost: e.cost }
     }
 
     pub fn get_potential(&self) -> Vec<F> {
@@ -222,28 +148,50 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         self.use_look_ahead_heuristic = b;
     }
 
+    pub fn use_price_update_heuristic(&mut self, b: bool) {
+        self.use_price_update_heuristic = b;
+    }
+
+    pub fn use_price_refinement_heuristic(&mut self, b: bool) {
+        self.use_price_refinement_heuristic = b;
+    }
+
+    // solves from scratch, so it can be called again after the supplies or the network change
     pub fn solve(&mut self) -> Status {
         self.status = Status::NotSolved;
+        self.optimal_cost = None;
 
         self.cost_scaling_factor = self.alpha * F::from_usize(self.num_of_nodes).unwrap();
 
         if self.num_of_nodes == 0 {
             self.status = Status::Optimal;
+            self.optimal_cost = Some(0);
             return Status::Optimal;
         }
 
+        // the excesses would overflow
+        if self.load().is_none() {
+            self.status = Status::BadCostRange;
+            return Status::BadCostRange;
+        }
+
         if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
             return Status::Unbalanced;
         }
 
         if self.check_feasibility && self.is_infeasible() {
+            self.status = Status::Infeasible;
             return Status::Infeasible;
         }
 
+        // a potential decreases by at most 3 * n * epsilon in each refine, and the epsilons sum to at most 2 * epsilon_0,
+        // so the reduced costs are at most (12 * n + 1) * epsilon_0 in absolute value
         let mut epsilon;
-        match self.gamma.checked_mul(&self.cost_scaling_factor) {
-            Some(p) => epsilon = F::max(F::one(), p),
-            None => {
+        let bound = F::from_usize(12 * self.num_of_nodes + 2);
+        match (self.gamma.checked_mul(&self.cost_scaling_factor), bound) {
+            (Some(p), Some(bound)) if p.checked_mul(&bound).is_some() => epsilon = F::max(F::one(), p),
+            _ => {
                 self.status = Status::BadCostRange;
                 return Status::BadCostRange;
             }
@@ -259,21 +207,14 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
 
         let mut num_loop = 0;
         loop {
-            let start = Instant::now();
-
             num_loop += 1;
             epsilon = F::max(epsilon / self.alpha, F::one());
-            eprintln!("epsilon: {}", epsilon);
 
-            if self.use_price_refinement_heuristic && num_loop > 1 && epsilon != F::one() {
-                if self.price_refinement(epsilon) {
-                    continue;
-                }
+            if self.use_price_refinement_heuristic && num_loop > 1 && epsilon != F::one() && self.price_refinement(epsilon) {
+                continue;
             }
 
             self.refine(epsilon);
-            let end = start.elapsed();
-            eprintln!("#time:{}.{:03}", end.as_secs(), end.subsec_nanos() / 1_000_000);
             // assert!(self.excess_is_valid());
             // assert!(self.is_feasible_flow());
             // assert!(self.is_epsilon_optimal(0, true));
@@ -297,19 +238,93 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         }
         self.optimal_cost = Some(cost / 2);
 
+        // the potentials of cost scaling are only epsilon-optimal, so recompute them from the optimal flow
         self.status = Status::Optimal;
-        // self.update_potential();
+        self.update_potential();
 
         Status::Optimal
     }
 
-    // TODO
-    pub fn solve_max_flow_with_min_cost() {}
+    // send as much flow as possible from source to sink in addition to the supplies, and minimize the cost of it
+    // the amount is added to the supply of source and sink. returns the status and the amount
+    pub fn solve_max_flow_with_min_cost(&mut self, source: usize, sink: usize) -> (Status, F) {
+        assert!(source < self.num_of_nodes);
+        assert!(sink < self.num_of_nodes);
+        assert_ne!(source, sink);
+
+        self.optimal_cost = None;
+        let load = match self.load() {
+            Some(load) => load,
+            None => {
+                self.status = Status::BadCostRange;
+                return (Status::BadCostRange, F::zero());
+            }
+        };
+        if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
+            return (Status::Unbalanced, F::zero());
+        }
+
+        // |amount| is at most the total capacity around source
+        let mut bound = F::to_i128(&self.initial_excess[source]).unwrap().abs();
+        for &(u, i) in self.edges.iter() {
+            let edge = &self.graph[u][i];
+            if (u == source) != (edge.to == source) {
+                bound = bound.saturating_add(i128::max(F::to_i128(&edge.lower).unwrap().abs(), F::to_i128(&edge.upper).unwrap().abs()));
+            }
+        }
+        // the edge sink -> source below adds 3 * bound to the load
+        let limit = (F::to_i128(&F::max_value()).unwrap() - load) / 3;
+        let is_capped = bound > limit;
+        let bound = F::from_i128(i128::min(bound, limit)).unwrap();
+
+        // a feasible amount is the flow of an additional edge sink -> source
+        let mut solver = self.lower_bound_solver();
+        let back = solver.add_edge(sink, source, -bound, bound);
+        let mut amount = match solver.solve() {
+            Some(flows) => flows[back],
+            None => {
+                self.status = Status::Infeasible;
+                return (Status::Infeasible, F::zero());
+            }
+        };
+
+        // the feasible amounts form an interval
+        let mut ng = bound + F::one();
+        while ng - amount > F::one() {
+            let mid = amount + (ng - amount) / F::from_i32(2).unwrap();
+            let mut solver = self.lower_bound_solver();
+            solver.add_supply(source, mid);
+            solver.add_supply(sink, -mid);
+            if solver.solve().is_some() {
+                amount = mid;
+            } else {
+                ng = mid;
+            }
+        }
+
+        // the amount might be larger than the excesses allow
+        if is_capped && amount == bound {
+            self.status = Status::BadCostRange;
+            return (Status::BadCostRange, F::zero());
+        }
+
+        self.add_supply(source, amount);
+        self.add_supply(sink, -amount);
+        (self.solve(), amount)
+    }
 
-    pub fn optimal_cost(&mut self) -> Option<i128> {
+    pub fn optimal_cost(&self) -> Option<i128> {
         self.optimal_cost
     }
 
+    // the optimal flow as paths from the nodes with supply to the nodes with demand and cycles (see decompose_flow)
+    pub fn decompose(&self) -> Vec<FlowPath<F>> {
+        assert_eq!(self.status, Status::Optimal);
+        let edges: Vec<Edge<F>> = (0..self.edges.len()).map(|i| self.get_directed_edge(EdgeId::new(i))).collect();
+        decompose_flow(&edges, &self.initial_excess)
+    }
+
     fn scale_cost(&mut self) {
         for u in 0..self.num_of_nodes {
             for edge in self.graph[u].iter_mut() {
@@ -342,26 +357,58 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         total != F::zero()
     }
 
+    // the supplies plus 2 * |lower| + |upper| of the edges other than self loops, if it fits in F
+    // it bounds the excesses of refine and of the feasibility check, and their sums
+    pub(crate) fn load(&self) -> Option<i128> {
+        let abs = |x: &F| F::to_i128(x).unwrap().checked_abs();
+        let mut load: i128 = 0;
+        for u in 0..self.num_of_nodes {
+            load = load.checked_add(abs(&self.initial_excess[u])?)?;
+        }
+        for &(u, i) in self.edges.iter() {
+            let edge = &self.graph[u][i];
+            if u != edge.to {
+                load = load.checked_add(abs(&edge.lower)?.checked_mul(2)?)?.checked_add(abs(&edge.upper)?)?;
+            }
+        }
+        F::from_i128(load).map(|_| load)
+    }
+
     fn is_infeasible(&self) -> bool {
+        self.lower_bound_solver().solve().is_none()
+    }
+
+    fn lower_bound_solver(&self) -> LowerBound<F> {
         let mut solver = LowerBound::new(self.num_of_nodes);
 
+        // a self loop can always carry its lower bound
         for u in 0..self.num_of_nodes {
             for i in 0..self.graph[u].len() {
                 let edge = &self.graph[u][i];
-                if !self.is_rev[u][i] {
-                    solver.add_edge(u, edge.to, F::to_i64(&edge.lower).unwrap(), F::to_i64(&edge.upper).unwrap());
+                if !self.is_rev[u][i] && edge.to != u {
+                    solver.add_edge(u, edge.to, edge.lower, edge.upper);
                 }
             }
         }
 
         for u in 0..self.num_of_nodes {
-            solver.add_supply(u, F::to_i64(&self.initial_excess[u]).unwrap());
+            solver.add_supply(u, self.initial_excess[u]);
         }
-        let status = solver.solve();
-        !status
+        solver
     }
 
+    // the flow of a previous solve is discarded
     fn initialize(&mut self) {
+        self.excess = self.initial_excess.clone();
+        self.potentials = vec![F::zero(); self.num_of_nodes];
+        self.active_nodes.clear();
+        self.num_relabel = 0;
+        for u in 0..self.num_of_nodes {
+            for edge in self.graph[u].iter_mut() {
+                edge.flow = F::zero();
+            }
+        }
+
         for u in 0..self.num_of_nodes {
             for i in 0..self.graph[u].len() {
                 let edge = &self.graph[u][i];
@@ -378,7 +425,7 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
             for i in 0..self.graph[u].len() {
                 let edge = &self.graph[u][i];
 
-                let reduced_cost = self.reduced_cost(u, &edge);
+                let reduced_cost = self.reduced_cost(u, edge);
                 if reduced_cost < F::zero() {
                     // 流量を上界にする
                     let flow = edge.residual_capacity();
@@ -404,12 +451,9 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
 
         // 0-optimal pseudo flow -> epsilon-optimal feasible flow
         while let Some(u) = self.active_nodes.pop_back() {
-            if self.use_price_update_heuristic {
-                if self.num_relabel > self.num_of_nodes as u64 {
-                    self.price_update(epsilon);
-                    self.num_relabel = 0;
-                    eprintln!("do price update");
-                }
+            if self.use_price_update_heuristic && self.num_relabel > self.num_of_nodes as u64 {
+                self.price_update(epsilon);
+                self.num_relabel = 0;
             }
 
             self.discharge(u, epsilon);
@@ -441,8 +485,11 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
 
         self.graph[u][i].flow += flow;
         self.graph[to][rev].flow -= flow;
-        self.excess[from] -= flow;
-        self.excess[to] += flow;
+        // the excess of a node may already be close to the limits of F, and a self loop does not change it
+        if from != to {
+            self.excess[from] -= flow;
+            self.excess[to] += flow;
+        }
     }
 
     fn reduced_cost(&self, u: usize, edge: &InternalEdge<F>) -> F {
@@ -467,16 +514,12 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
                 continue;
             }
 
-            if self.is_admissible(u, &edge, epsilon) {
+            if self.is_admissible(u, edge, epsilon) {
                 let to = edge.to;
 
-                if self.use_look_ahead_heuristic {
-                    if !self.look_ahead(to, epsilon) {
-                        // toがrelabelしているので，edgeがadmissibleかチェックする
-                        if !self.is_admissible(u, &self.graph[u][i], epsilon) {
-                            continue;
-                        }
-                    }
+                // toがrelabelしているので，edgeがadmissibleかチェックする
+                if self.use_look_ahead_heuristic && !self.look_ahead(to, epsilon) && !self.is_admissible(u, &self.graph[u][i], epsilon) {
+                    continue;
                 }
 
                 let flow = F::min(self.graph[u][i].residual_capacity(), self.excess[u]);
@@ -565,7 +608,7 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
                 continue;
             }
 
-            if self.is_admissible(u, &edge, epsilon) {
+            if self.is_admissible(u, edge, epsilon) {
                 self.current_edges[u] = i;
                 return true;
             }
@@ -605,9 +648,7 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         }
 
         // update potential
-        for u in 0..self.num_of_nodes {
-            self.potentials[u] = p[u];
-        }
+        self.potentials.copy_from_slice(&p);
         true
     }
 
@@ -694,7 +735,7 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
                     }
 
                     let rev_edge = &self.graph[u][edge.rev]; // u -> v
-                    let x = (self.reduced_cost(u, rev_edge) / epsilon + F::one());
+                    let x = self.reduced_cost(u, rev_edge) / epsilon + F::one();
                     // eprintln!("x:{}", x);
                     let mut new_distance = F::to_i64(&x).unwrap();
                     new_distance = i64::min(i64::max(new_distance, 1), inf as i64);
@@ -757,41 +798,128 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         }
     }
 
+    // increase the upper bound of the edge by one and keep the flow optimal
+    // an edge with upper bound F::max_value() is left as it is
     pub fn increase_capacity_unit(&mut self, edge_id: EdgeId) {
         assert_eq!(self.status, Status::Optimal);
-        let (u, i) = (edge_id.0, edge_id.1);
+        let (u, i) = self.edges[edge_id.index()];
+        if self.graph[u][i].upper == F::max_value() {
+            return;
+        }
 
-        self.graph[u][i].upper += F::one();
-        if self.graph[u][i].flow < self.graph[u][i].upper - F::one() {
+        if self.graph[u][i].flow < self.graph[u][i].upper {
+            self.graph[u][i].upper += F::one();
             return;
         }
 
+        // the potentials of the old network, which has no negative cycle
         self.update_potential();
+        self.graph[u][i].upper += F::one();
 
         // it satisfies the reduced cost optimality conditions
         if self.reduced_cost(u, &self.graph[u][i]) >= F::zero() {
             return;
         }
 
-        // 流量を上界にする
-        self.push_flow(u, i, F::one());
-        assert_eq!(self.graph[u][i].flow, self.graph[u][i].upper);
-
-        // find shortest path from v to u
+        // the new unit is worth sending if it closes a negative cycle with the shortest path v -> u
         let v = self.graph[u][i].to;
+        let mut cycle = vec![(u, i)];
+        if v != u {
+            match self.shortest_path(v, u) {
+                Some((distance, path)) if distance + self.reduced_cost(u, &self.graph[u][i]) < F::zero() => cycle.extend(path),
+                _ => return,
+            }
+        }
+
+        let mut cost = 0;
+        for &(x, j) in cycle.iter() {
+            cost += F::to_i128(&self.graph[x][j].cost).unwrap();
+            self.push_flow(x, j, F::one());
+        }
+        self.optimal_cost = self.optimal_cost.map(|c| c + cost);
+        self.update_potential();
+    }
+
+    // dijkstra on the residual network with the reduced costs, which must be non-negative except for the edges out of t
+    // returns the distance and the edges (node, index) of the path
+    fn shortest_path(&self, s: usize, t: usize) -> Option<(F, Vec<(usize, usize)>)> {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let mut distance = vec![None; self.num_of_nodes];
+        let mut prev = vec![None; self.num_of_nodes];
+        let mut heap = BinaryHeap::new();
+        distance[s] = Some(F::zero());
+        heap.push((Reverse(F::zero()), s));
+
+        while let Some((Reverse(d), x)) = heap.pop() {
+            if distance[x] != Some(d) {
+                continue;
+            }
+            if x == t {
+                let mut path = Vec::new();
+                let mut y = t;
+                while let Some((z, j)) = prev[y] {
+                    path.push((z, j));
+                    y = z;
+                }
+                path.reverse();
+                return Some((d, path));
+            }
+
+            for (j, edge) in self.graph[x].iter().enumerate() {
+                if edge.residual_capacity() > F::zero() {
+                    let new_distance = d + self.reduced_cost(x, edge);
+                    if distance[edge.to].is_none_or(|old| new_distance < old) {
+                        distance[edge.to] = Some(new_distance);
+                        prev[edge.to] = Some((x, j));
+                        heap.push((Reverse(new_distance), edge.to));
+                    }
+                }
+            }
+        }
+        None
     }
 
+    // decrease the upper bound of the edge by one and keep the flow optimal
+    // a unit above the new bound is rerouted along a shortest path, and the status becomes Infeasible if there is none
     pub fn decrease_capacity(&mut self, edge_id: EdgeId) {
         assert_eq!(self.status, Status::Optimal);
-        assert!(self.graph[edge_id.0][edge_id.1].upper >= F::one());
+        let (u, i) = self.edges[edge_id.index()];
+        assert!(self.graph[u][i].upper >= F::one());
+        assert!(self.graph[u][i].upper > self.graph[u][i].lower, "the upper bound must stay at least the lower bound");
 
-        self.graph[edge_id.0][edge_id.1].upper -= F::one();
+        if self.graph[u][i].flow < self.graph[u][i].upper {
+            self.graph[u][i].upper -= F::one();
+            return;
+        }
 
-        let edge = &self.graph[edge_id.0][edge_id.1];
+        // the potentials of the old network. the edge is saturated, so taking the unit off it adds no residual edge
+        // and the reduced costs of the residual edges stay non-negative
+        self.update_potential();
+        self.graph[u][i].upper -= F::one();
+        let mut cost = -F::to_i128(&self.graph[u][i].cost).unwrap();
+        self.push_flow(u, i, -F::one());
 
-        if edge.flow <= edge.upper {
-            return;
+        // the unit now has to go from u to v some other way
+        let v = self.graph[u][i].to;
+        if v != u {
+            match self.shortest_path(u, v) {
+                Some((_, path)) => {
+                    for &(x, j) in path.iter() {
+                        cost += F::to_i128(&self.graph[x][j].cost).unwrap();
+                        self.push_flow(x, j, F::one());
+                    }
+                }
+                None => {
+                    self.status = Status::Infeasible;
+                    self.optimal_cost = None;
+                    return;
+                }
+            }
         }
+        self.optimal_cost = self.optimal_cost.map(|c| c + cost);
+        self.update_potential();
     }
 
     // debug
@@ -855,20 +983,14 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
                 }
 
                 let reduced_cost = self.reduced_cost(u, edge);
-                if reduced_cost > epsilon {
-                    if edge.flow != edge.lower {
-                        return false;
-                    }
+                if reduced_cost > epsilon && edge.flow != edge.lower {
+                    return false;
                 }
-                if -epsilon <= reduced_cost && reduced_cost <= epsilon {
-                    if !(edge.lower <= edge.flow && edge.flow <= edge.upper) {
-                        return false;
-                    }
+                if -epsilon <= reduced_cost && reduced_cost <= epsilon && !(edge.lower <= edge.flow && edge.flow <= edge.upper) {
+                    return false;
                 }
-                if reduced_cost < epsilon {
-                    if edge.flow != edge.upper {
-                        return false;
-                    }
+                if reduced_cost < epsilon && edge.flow != edge.upper {
+                    return false;
                 }
             }
         }
@@ -915,3 +1037,37 @@ impl<F: Flow + std::ops::Neg<Output = F>> CostScalingPushRelabel<F> {
         true
     }
 }
+
+impl<F: Flow> MinCostFlowSolver<F> for CostScalingPushRelabel<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        CostScalingPushRelabel::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        CostScalingPushRelabel::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        CostScalingPushRelabel::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        CostScalingPushRelabel::solve(self)
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        CostScalingPushRelabel::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        CostScalingPushRelabel::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        CostScalingPushRelabel::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+}
@@ -0,0 +1,271 @@
+use crate::CostScalingPushRelabel;
+use minimum_cost_flow_common::{Flow, Status};
+use std::collections::BTreeMap;
+
+// a breakpoint of the minimum cost as a function of the amount shipped
+// marginal_cost is the cost of each unit up to the next breakpoint, None for the largest amount
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Breakpoint<F: Flow> {
+    pub amount: F,
+    pub cost: i128,
+    pub marginal_cost: Option<i128>,
+}
+
+impl<F: Flow> CostScalingPushRelabel<F> {
+    // the minimum cost of shipping amount = 0, 1, ... from source to sink in addition to the supplies
+    // each shortest path from source to sink on the optimal flow is a linear piece of the curve. the amounts are
+    // limited so that the supplies and the capacities still fit in F (see load). self is not modified
+    pub fn parametric_flow(&self, source: usize, sink: usize) -> Result<Vec<Breakpoint<F>>, Status> {
+        assert!(source < self.num_of_nodes);
+        assert!(sink < self.num_of_nodes);
+        assert_ne!(source, sink);
+
+        let mut solver = self.solved()?;
+        let limit = solver.shipment_limit();
+        Ok(solver.ship(source, sink, limit))
+    }
+
+    // send as much flow as possible from source to sink in addition to the supplies, for a total cost of at most budget
+    // the amount is added to the supply of source and sink, and the flow is the cheapest one for it
+    // returns the status and the amount. Infeasible if every amount costs more than budget
+    pub fn solve_max_flow_with_budget(&mut self, source: usize, sink: usize, budget: i128) -> (Status, F) {
+        assert!(source < self.num_of_nodes);
+        assert!(sink < self.num_of_nodes);
+        assert_ne!(source, sink);
+
+        // the supplies or the edges may have changed since an earlier solve, so solve from scratch
+        self.optimal_cost = None;
+        let mut solver = match self.unsolved_copy().solved() {
+            Ok(solver) => solver,
+            Err(status) => {
+                self.status = status;
+                return (status, F::zero());
+            }
+        };
+        let curve = solver.clone().ship(source, sink, solver.shipment_limit());
+
+        // the cost is convex, so it is within the budget up to some amount on the piece after the last
+        // breakpoint within the budget
+        let amount = match curve.iter().rposition(|breakpoint| breakpoint.cost <= budget) {
+            Some(k) => match curve[k].marginal_cost {
+                Some(marginal_cost) => F::to_i128(&curve[k].amount).unwrap() + (budget - curve[k].cost) / marginal_cost,
+                None => F::to_i128(&curve[k].amount).unwrap(),
+            },
+            None => {
+                self.status = Status::Infeasible;
+                return (Status::Infeasible, F::zero());
+            }
+        };
+
+        solver.ship(source, sink, amount);
+        *self = solver;
+        (Status::Optimal, F::from_i128(amount).unwrap())
+    }
+
+    // the minimum cost with the supplies supply + amount * direction, for every amount = 0, 1, ... that is feasible
+    // the cost is a convex function of the amount, and the breakpoints are those of its piecewise-linear
+    // interpolation (for a source and a sink they are integral, so it is the exact parametric curve)
+    // returns the status of amount = 0 if it is not optimal. self is not modified
+    pub fn parametric_supply(&self, direction: &[F]) -> Result<Vec<Breakpoint<F>>, Status> {
+        assert_eq!(direction.len(), self.num_of_nodes);
+        let (positive, negative) = direction.iter().fold((0i128, 0i128), |(positive, negative), d| match F::to_i128(d).unwrap() {
+            d if d > 0 => (positive.saturating_add(d), negative),
+            d => (positive, negative.saturating_sub(d)),
+        });
+        assert_eq!(positive, negative, "direction must sum to 0");
+        assert!(positive > 0, "direction must not be 0");
+
+        let solver = self.solved()?;
+        let mut costs = BTreeMap::new();
+        costs.insert(0, solver.optimal_cost.unwrap());
+        let max_amount = solver.max_amount(direction);
+
+        // eisner-severance: a piece [a, b] is linear if the tangent at a (slope sa) reaches f(b), otherwise it is
+        // split at the intersection of the tangents at a and b, where the breakpoint is if there is only one
+        if max_amount > 0 {
+            let fb = solver.parametric_cost(direction, max_amount, &mut costs)?;
+            let sa = solver.parametric_cost(direction, 1, &mut costs)? - costs[&0];
+            let sb = fb - solver.parametric_cost(direction, max_amount - 1, &mut costs)?;
+            let mut pieces = vec![(0, max_amount, sa, sb)];
+            while let Some((a, b, sa, sb)) = pieces.pop() {
+                let (fa, fb) = (costs[&a], costs[&b]);
+                if fb - fa == sa * (b - a) {
+                    continue;
+                }
+
+                // sa < sb because the cost is convex and the piece is not linear
+                let x = (fb - fa + sa * a - sb * b).div_euclid(sa - sb);
+                let c = i128::min(i128::max(x, a + 1), b - 1);
+                let fc = solver.parametric_cost(direction, c, &mut costs)?;
+                let left = fc - solver.parametric_cost(direction, c - 1, &mut costs)?;
+                let right = solver.parametric_cost(direction, c + 1, &mut costs)? - fc;
+                pieces.push((a, c, sa, left));
+                pieces.push((c, b, right, sb));
+            }
+        }
+
+        // the evaluated amounts split the curve into linear pieces. keep the ends and where the slope changes
+        let points: Vec<(i128, i128)> = costs.into_iter().collect();
+        let mut breakpoints: Vec<Breakpoint<F>> = Vec::new();
+        for k in 0..points.len() {
+            let (amount, cost) = points[k];
+            let marginal_cost = points.get(k + 1).map(|&(next, next_cost)| (next_cost - cost) / (next - amount));
+            if k > 0 && marginal_cost.is_some() && breakpoints.last().unwrap().marginal_cost == marginal_cost {
+                continue;
+            }
+            breakpoints.push(Breakpoint { amount: F::from_i128(amount).unwrap(), cost, marginal_cost });
+        }
+        Ok(breakpoints)
+    }
+
+    // the optimal cost with the supplies moved by amount * direction. self must be solved, and the excesses are sent
+    // to the deficits along shortest paths from the optimal flow
+    fn parametric_cost(&self, direction: &[F], amount: i128, costs: &mut BTreeMap<i128, i128>) -> Result<i128, Status> {
+        if let Some(&cost) = costs.get(&amount) {
+            return Ok(cost);
+        }
+
+        let mut solver = self.clone();
+        for u in 0..self.num_of_nodes {
+            if direction[u] != F::zero() {
+                solver.add_supply(u, F::from_i128(amount * F::to_i128(&direction[u]).unwrap()).unwrap());
+            }
+        }
+        // an excess that reaches no deficit can not be sent at all
+        for s in 0..self.num_of_nodes {
+            for t in 0..self.num_of_nodes {
+                while solver.excess[s] > F::zero() && solver.excess[t] < F::zero() {
+                    let limit = i128::min(F::to_i128(&solver.excess[s]).unwrap(), -F::to_i128(&solver.excess[t]).unwrap());
+                    if solver.augment(s, t, limit).is_none() {
+                        break;
+                    }
+                }
+            }
+            if solver.excess[s] > F::zero() {
+                return Err(Status::Infeasible);
+            }
+        }
+
+        let cost = solver.optimal_cost.unwrap();
+        costs.insert(amount, cost);
+        Ok(cost)
+    }
+
+    // the largest feasible amount, where amount = 0 is feasible. the feasible amounts form an interval
+    // they are limited so that the load still fits in F
+    fn max_amount(&self, direction: &[F]) -> i128 {
+        // |supply + amount * direction| at a node is at most the total capacity around it
+        let mut around: Vec<i128> = self.initial_excess.iter().map(|s| F::to_i128(s).unwrap().abs()).collect();
+        for &(u, i) in self.edges.iter() {
+            let edge = &self.graph[u][i];
+            if u != edge.to {
+                let capacity = i128::max(F::to_i128(&edge.lower).unwrap().abs(), F::to_i128(&edge.upper).unwrap().abs());
+                around[u] = around[u].saturating_add(capacity);
+                around[edge.to] = around[edge.to].saturating_add(capacity);
+            }
+        }
+        let size = direction.iter().fold(0i128, |size, d| size.saturating_add(F::to_i128(d).unwrap().abs()));
+        let mut ng = (F::to_i128(&F::max_value()).unwrap() - self.load().unwrap()) / size;
+        for u in 0..self.num_of_nodes {
+            let d = F::to_i128(&direction[u]).unwrap().abs();
+            if d != 0 {
+                ng = i128::min(ng, around[u] / d);
+            }
+        }
+
+        let mut ok = 0;
+        ng += 1;
+        while ng - ok > 1 {
+            let mid = ok + (ng - ok) / 2;
+            let mut solver = self.lower_bound_solver();
+            for u in 0..self.num_of_nodes {
+                solver.add_supply(u, F::from_i128(mid * F::to_i128(&direction[u]).unwrap()).unwrap());
+            }
+            if solver.solve().is_some() {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        ok
+    }
+
+    // each unit shipped from a source to a sink adds at most 2 to the load, which must still fit in F
+    fn shipment_limit(&self) -> i128 {
+        (F::to_i128(&F::max_value()).unwrap() - self.load().unwrap()) / 2
+    }
+
+    // ship up to limit units from source to sink along shortest paths, and add them to the supplies. self must be solved
+    // each path is a linear piece of the cost. returns the breakpoints of the cost of the units shipped
+    fn ship(&mut self, source: usize, sink: usize, limit: i128) -> Vec<Breakpoint<F>> {
+        let mut amount = 0;
+        let mut breakpoints: Vec<Breakpoint<F>> = Vec::new();
+        while amount < limit {
+            let cost = self.optimal_cost.unwrap();
+            let (flow, marginal_cost) = match self.augment(source, sink, limit - amount) {
+                Some(augmented) => augmented,
+                None => break,
+            };
+            self.add_supply(source, F::from_i128(flow).unwrap());
+            self.add_supply(sink, -F::from_i128(flow).unwrap());
+
+            if breakpoints.last().is_none_or(|breakpoint| breakpoint.marginal_cost != Some(marginal_cost)) {
+                breakpoints.push(Breakpoint { amount: F::from_i128(amount).unwrap(), cost, marginal_cost: Some(marginal_cost) });
+            }
+            amount += flow;
+        }
+        breakpoints.push(Breakpoint { amount: F::from_i128(amount).unwrap(), cost: self.optimal_cost.unwrap(), marginal_cost: None });
+        breakpoints
+    }
+
+    // a copy of self with an optimal flow and exact potentials, solving an unsolved copy unless self is optimal
+    fn solved(&self) -> Result<Self, Status> {
+        let mut solver = if self.status == Status::Optimal { self.clone() } else { self.unsolved_copy() };
+        if solver.status != Status::Optimal {
+            solver.solve();
+        }
+        match solver.status {
+            Status::Optimal => {
+                solver.update_potential();
+                Ok(solver)
+            }
+            status => Err(status),
+        }
+    }
+
+    // push up to limit units along a shortest path from s to t, which keeps the flow optimal for the new excesses
+    // returns the units and the cost of each, or None if t is not reachable
+    fn augment(&mut self, s: usize, t: usize, limit: i128) -> Option<(i128, i128)> {
+        let (_, path) = self.shortest_path(s, t)?;
+        let mut flow = limit;
+        let mut cost = 0;
+        for &(u, i) in path.iter() {
+            flow = i128::min(flow, F::to_i128(&self.graph[u][i].residual_capacity()).unwrap());
+            cost += F::to_i128(&self.graph[u][i].cost).unwrap();
+        }
+        for &(u, i) in path.iter() {
+            self.push_flow(u, i, F::from_i128(flow).unwrap());
+        }
+        self.optimal_cost = self.optimal_cost.map(|c| c + flow * cost);
+        self.update_potential();
+        Some((flow, cost))
+    }
+
+    // a copy with the same edges, supplies and settings, without flow
+    pub(crate) fn unsolved_copy(&self) -> Self {
+        let mut solver = CostScalingPushRelabel::new(self.num_of_nodes);
+        for &(u, i) in self.edges.iter() {
+            let edge = &self.graph[u][i];
+            solver.add_directed_edge(u, edge.to, edge.lower, edge.upper, edge.cost);
+        }
+        for u in 0..self.num_of_nodes {
+            solver.add_supply(u, self.initial_excess[u]);
+        }
+        solver.alpha = self.alpha;
+        solver.check_feasibility = self.check_feasibility;
+        solver.use_look_ahead_heuristic = self.use_look_ahead_heuristic;
+        solver.use_price_update_heuristic = self.use_price_update_heuristic;
+        solver.use_price_refinement_heuristic = self.use_price_refinement_heuristic;
+        solver
+    }
+}
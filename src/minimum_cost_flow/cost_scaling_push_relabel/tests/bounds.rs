@@ -0,0 +1,70 @@
+// the supplies plus 2 * |lower| + |upper| of the edges other than self loops must fit in F, because they bound the
+// excesses. the costs times alpha * n * (12 * n + 2) must fit too
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use minimum_cost_flow_common::{MinCostFlowProblem, Status};
+
+#[test]
+fn rejects_capacities_beyond_the_load() {
+    // the negative cycle is saturated, but the excesses on the way can reach 2 * i64::MAX
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, i64::MAX, -1);
+    problem.add_directed_edge(1, 0, 0, i64::MAX, -1);
+    problem.add_directed_edge(0, 2, 0, 10, 1);
+    problem.add_supply(0, 5);
+    problem.add_supply(2, -5);
+    assert_eq!(problem.solve::<CostScalingPushRelabel<i64>>().status, Status::BadCostRange);
+
+    // the load takes precedence over the other statuses
+    problem.add_supply(0, 1);
+    assert_eq!(problem.solve::<CostScalingPushRelabel<i64>>().status, Status::BadCostRange);
+}
+
+#[test]
+fn solves_up_to_the_load() {
+    // 3 + 3 + (2 * 2 + 5) + 5 + (i32::MAX - 20) is exactly i32::MAX
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, -2, 5, -1);
+    problem.add_directed_edge(1, 2, 0, 5, 1);
+    problem.add_directed_edge(2, 0, 0, i32::MAX - 20, -1);
+    problem.add_supply(0, 3);
+    problem.add_supply(2, -3);
+    let solution = problem.solve::<CostScalingPushRelabel<i32>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.flows, vec![5, 5, 2]);
+    assert_eq!(solution.cost, Some(-5 + 5 - 2));
+    problem.check_solution(&solution).unwrap();
+
+    problem.add_supply(1, 1);
+    problem.add_supply(2, -1);
+    assert_eq!(problem.solve::<CostScalingPushRelabel<i32>>().status, Status::BadCostRange);
+}
+
+#[test]
+fn ignores_self_loops() {
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 0, 0, i64::MAX, -1);
+    problem.add_directed_edge(1, 1, i64::MAX, i64::MAX, 1);
+    problem.add_directed_edge(0, 1, 0, 10, 1);
+    problem.add_supply(0, 5);
+    problem.add_supply(1, -5);
+    let solution = problem.solve::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.flows, vec![i64::MAX, i64::MAX, 5]);
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn max_flow_with_min_cost_stays_within_the_load() {
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, i64::MAX, 1);
+    let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve_max_flow_with_min_cost(0, 1), (Status::BadCostRange, 0));
+    assert_eq!(solver.optimal_cost(), None);
+
+    // 3 * amount for the feasibility checks
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, i64::MAX / 4, 1);
+    let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve_max_flow_with_min_cost(0, 1), (Status::Optimal, i64::MAX / 4));
+    assert_eq!(solver.optimal_cost(), Some((i64::MAX / 4) as i128));
+}
@@ -0,0 +1,75 @@
+use cost_scaling_push_relabel::{CostScalingPushRelabel, EdgeId, MinCostFlowSolver, Status};
+use minimum_cost_flow_common::{MinCostFlowProblem, Solution};
+use test_support::{brute_force, random_problem, Random};
+
+#[test]
+fn decreases_capacity() {
+    // 2 units on 0 -> 2 at -1 and 2 on 0 -> 1 -> 2 at 1 + 2. the self loop is full at -3
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 2, 0, 2, -1);
+    problem.add_directed_edge(0, 1, 0, 3, 1);
+    problem.add_directed_edge(1, 2, 0, 4, 2);
+    problem.add_directed_edge(0, 2, 0, 1, 5);
+    problem.add_directed_edge(1, 1, 0, 2, -3);
+    problem.add_supply(0, 4);
+    problem.add_supply(2, -4);
+    let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(4 - 6));
+
+    // each unit off 0 -> 2 takes the next cheapest path
+    solver.decrease_capacity(EdgeId::new(0));
+    problem.edges[0].upper = 1;
+    assert_eq!(solver.optimal_cost(), Some(8 - 6));
+    problem.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+
+    solver.decrease_capacity(EdgeId::new(0));
+    problem.edges[0].upper = 0;
+    assert_eq!(solver.optimal_cost(), Some(14 - 6));
+    let solution = Solution::from_solver(&solver, &edge_ids);
+    assert_eq!(solution.flows, vec![0, 3, 3, 1, 2]);
+    problem.check_solution(&solution).unwrap();
+
+    // a self loop just carries less, and an edge with room is only tightened
+    solver.decrease_capacity(EdgeId::new(4));
+    solver.decrease_capacity(EdgeId::new(2));
+    problem.edges[4].upper = 1;
+    problem.edges[2].upper = 3;
+    assert_eq!(solver.optimal_cost(), Some(14 - 3));
+    problem.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+
+    // nothing else leaves 0
+    solver.decrease_capacity(EdgeId::new(1));
+    assert_eq!(MinCostFlowSolver::status(&solver), Status::Infeasible);
+    assert_eq!(solver.optimal_cost(), None);
+}
+
+#[test]
+fn decrease_capacity_matches_brute_force() {
+    let mut random = Random(0x2545f4914f6cdd1d);
+    for case in 0..5000 {
+        let problem = random_problem(&mut random);
+        if brute_force(&problem).0 != Status::Optimal {
+            continue;
+        }
+
+        for e in 0..problem.num_of_edges() {
+            let edge = &problem.edges[e];
+            if edge.upper < 1 || edge.upper == edge.lower {
+                continue;
+            }
+            let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+            assert_eq!(solver.solve(), Status::Optimal);
+            solver.decrease_capacity(edge_ids[e]);
+
+            let mut decreased = problem.clone();
+            decreased.edges[e].upper -= 1;
+            let (status, cost) = brute_force(&decreased);
+            assert_eq!(MinCostFlowSolver::status(&solver), status, "case {} edge {}: {:?}", case, e, problem);
+            assert_eq!(solver.optimal_cost(), cost, "case {} edge {}: {:?}", case, e, problem);
+            if status == Status::Optimal {
+                decreased.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// differential tests of CostScalingPushRelabel against exhaustive enumeration of the flows
+// the networks are tiny (at most 4 nodes, 6 edges and 4 flow values per edge) and have
+// lower bounds (possibly negative), negative costs, self loops and parallel edges
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use minimum_cost_flow_common::{EdgeId, MinCostFlowProblem, MinCostFlowSolver, Solution, Status};
+use test_support::{brute_force, random_problem, Random};
+
+#[test]
+fn solve_matches_brute_force() {
+    let mut random = Random(0x9e3779b97f4a7c15);
+    for case in 0..20000 {
+        let problem = random_problem(&mut random);
+        let (status, cost) = brute_force(&problem);
+
+        // solve recomputes exact potentials, which check_solution verifies
+        let solution = problem.solve::<CostScalingPushRelabel<i64>>();
+        assert_eq!(solution.status, status, "case {}: {:?}", case, problem);
+        if status == Status::Optimal {
+            assert_eq!(solution.cost, cost, "case {}: {:?}", case, problem);
+            problem.check_solution(&solution).unwrap_or_else(|e| panic!("case {}: {}: {:?}", case, e, problem));
+        }
+    }
+}
+
+#[test]
+fn heuristics_match_brute_force() {
+    let mut random = Random(0x2545f4914f6cdd1d);
+    for case in 0..3000 {
+        let problem = random_problem(&mut random);
+        let (status, cost) = brute_force(&problem);
+
+        for bits in 0..16 {
+            let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+            solver.use_look_ahead_heuristic(bits & 1 != 0);
+            solver.use_price_update_heuristic(bits & 2 != 0);
+            solver.use_price_refinement_heuristic(bits & 4 != 0);
+            solver.set_alpha(if bits & 8 != 0 { 2 } else { 5 });
+
+            assert_eq!(CostScalingPushRelabel::solve(&mut solver), status, "case {} heuristics {}: {:?}", case, bits, problem);
+            if status == Status::Optimal {
+                assert_eq!(solver.optimal_cost(), cost, "case {} heuristics {}: {:?}", case, bits, problem);
+                assert_potentials_are_valid(&solver, &problem);
+            }
+        }
+    }
+}
+
+// cost + potential[from] - potential[to] >= 0 on every residual edge
+fn assert_potentials_are_valid(solver: &CostScalingPushRelabel<i64>, problem: &MinCostFlowProblem<i64>) {
+    let potentials = solver.get_potential();
+    for i in 0..problem.num_of_edges() {
+        let e = solver.get_directed_edge(EdgeId::new(i));
+        let reduced_cost = e.cost + potentials[e.from] - potentials[e.to];
+        assert!(e.flow == e.upper || reduced_cost >= 0, "edge {} {:?} has reduced cost {}: {:?}", i, e, reduced_cost, problem);
+        assert!(e.flow == e.lower || reduced_cost <= 0, "edge {} {:?} has reduced cost {}: {:?}", i, e, reduced_cost, problem);
+    }
+}
+
+#[test]
+fn solve_again_matches_brute_force() {
+    let mut random = Random(0x5851f42d4c957f2d);
+    for case in 0..3000 {
+        let mut problem = random_problem(&mut random);
+        let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+        MinCostFlowSolver::solve(&mut solver);
+
+        // the flow of the first solve is discarded
+        let u = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        let v = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        problem.add_supply(u, 1);
+        problem.add_supply(v, -1);
+        solver.add_supply(u, 1);
+        solver.add_supply(v, -1);
+
+        let (status, cost) = brute_force(&problem);
+        assert_eq!(MinCostFlowSolver::solve(&mut solver), status, "case {}: {:?}", case, problem);
+        assert_eq!(solver.optimal_cost(), cost, "case {}: {:?}", case, problem);
+        if status == Status::Optimal {
+            problem.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap_or_else(|e| panic!("case {}: {}: {:?}", case, e, problem));
+        }
+    }
+}
+
+#[test]
+fn max_flow_with_min_cost_after_solve() {
+    // the first edge is fixed at 2, and the second one carries the additional amount
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 2, 2, 0);
+    problem.add_directed_edge(0, 1, -2, 2, -5);
+    problem.add_supply(0, 2);
+    problem.add_supply(1, -2);
+    let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(0));
+
+    assert_eq!(solver.solve_max_flow_with_min_cost(0, 1), (Status::Optimal, 2));
+    assert_eq!(solver.optimal_cost(), Some(-10));
+    problem.add_supply(0, 2);
+    problem.add_supply(1, -2);
+    problem.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+
+    let mut random = Random(0x14057b7ef767814f);
+    for case in 0..500 {
+        let problem = random_problem(&mut random);
+        let s = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        let t = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        if s == t {
+            continue;
+        }
+
+        // the largest amount with a feasible flow
+        let mut expected = (Status::Infeasible, None, 0);
+        for amount in (-40..=40).rev() {
+            let mut shifted = problem.clone();
+            shifted.add_supply(s, amount);
+            shifted.add_supply(t, -amount);
+            let (status, cost) = brute_force(&shifted);
+            if status != Status::Infeasible {
+                expected = (status, cost, amount);
+                break;
+            }
+        }
+
+        let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+        CostScalingPushRelabel::solve(&mut solver);
+        let (status, amount) = solver.solve_max_flow_with_min_cost(s, t);
+        assert_eq!(status, expected.0, "case {}: {:?}", case, problem);
+        assert_eq!(solver.optimal_cost(), expected.1, "case {}: {:?}", case, problem);
+        if status == Status::Optimal {
+            assert_eq!(amount, expected.2, "case {}: {:?}", case, problem);
+        }
+    }
+}
+
+#[test]
+fn parametric_flow_matches_brute_force() {
+    let mut random = Random(0xda942042e4dd58b5);
+    for case in 0..1000 {
+        let problem = random_problem(&mut random);
+        let s = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        let t = random.next(0, problem.num_of_nodes as i64 - 1) as usize;
+        if s == t {
+            continue;
+        }
+
+        // from an unsolved and from a solved network
+        let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+        let curve = solver.parametric_flow(s, t);
+        CostScalingPushRelabel::solve(&mut solver);
+        assert_eq!(solver.parametric_flow(s, t), curve, "case {}: {:?}", case, problem);
+        let mut direction = vec![0; problem.num_of_nodes];
+        direction[s] = 1;
+        direction[t] = -1;
+        assert_eq!(solver.parametric_supply(&direction), curve, "case {}: {:?}", case, problem);
+
+        let curve = match curve {
+            Ok(curve) => curve,
+            Err(status) => {
+                assert_eq!(brute_force(&problem).0, status, "case {}: {:?}", case, problem);
+                continue;
+            }
+        };
+        let last = curve.last().unwrap().amount;
+        for amount in 0..=last + 1 {
+            let mut shifted = problem.clone();
+            shifted.add_supply(s, amount);
+            shifted.add_supply(t, -amount);
+            let expected = if amount > last {
+                (Status::Infeasible, None)
+            } else {
+                let k = curve.iter().rposition(|breakpoint| breakpoint.amount <= amount).unwrap();
+                let cost = curve[k].cost + curve[k].marginal_cost.unwrap_or(0) * (amount - curve[k].amount) as i128;
+                (Status::Optimal, Some(cost))
+            };
+            assert_eq!(brute_force(&shifted), expected, "case {} amount {}: {:?}", case, amount, problem);
+        }
+    }
+}
+
+#[test]
+fn parametric_supply_matches_brute_force() {
+    let mut random = Random(0x94d049bb133111eb);
+    for case in 0..1000 {
+        let problem = random_problem(&mut random);
+        let n = problem.num_of_nodes;
+        let mut direction: Vec<i64> = (0..n).map(|_| random.next(-2, 2)).collect();
+        direction[n - 1] -= direction.iter().sum::<i64>();
+        if direction.iter().all(|&d| d == 0) {
+            continue;
+        }
+
+        let (solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+        let curve = match solver.parametric_supply(&direction) {
+            Ok(curve) => curve,
+            Err(status) => {
+                assert_eq!(brute_force(&problem).0, status, "case {}: {:?}", case, problem);
+                continue;
+            }
+        };
+        let last = curve.last().unwrap().amount;
+        for amount in 0..=last + 1 {
+            let mut shifted = problem.clone();
+            for (u, &d) in direction.iter().enumerate() {
+                shifted.add_supply(u, amount * d);
+            }
+            let expected = if amount > last {
+                (Status::Infeasible, None)
+            } else {
+                let k = curve.iter().rposition(|breakpoint| breakpoint.amount <= amount).unwrap();
+                let cost = curve[k].cost + curve[k].marginal_cost.unwrap_or(0) * (amount - curve[k].amount) as i128;
+                (Status::Optimal, Some(cost))
+            };
+            assert_eq!(brute_force(&shifted), expected, "case {} amount {}: {:?} {:?}", case, amount, direction, problem);
+        }
+    }
+}
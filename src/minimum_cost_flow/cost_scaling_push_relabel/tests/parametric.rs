@@ -0,0 +1,163 @@
+use cost_scaling_push_relabel::{Breakpoint, CostScalingPushRelabel, EdgeId, MinCostFlowSolver, Status};
+use minimum_cost_flow_common::{MinCostFlowProblem, Solution};
+
+fn breakpoint(amount: i64, cost: i128, marginal_cost: Option<i128>) -> Breakpoint<i64> {
+    Breakpoint { amount, cost, marginal_cost }
+}
+
+// 0 -> 2 is 2 units at -1, then 0 -> 1 -> 2 is 3 units at 1 + 2, then 0 -> 2 is 1 unit at 5
+fn network() -> CostScalingPushRelabel<i64> {
+    let mut solver = CostScalingPushRelabel::new(3);
+    solver.add_directed_edge(0, 2, 0, 2, -1);
+    solver.add_directed_edge(0, 1, 0, 3, 1);
+    solver.add_directed_edge(1, 2, 0, 3, 2);
+    solver.add_directed_edge(0, 2, 0, 1, 5);
+    solver
+}
+
+#[test]
+fn parametric_flow() {
+    let curve = vec![breakpoint(0, 0, Some(-1)), breakpoint(2, -2, Some(3)), breakpoint(5, 7, Some(5)), breakpoint(6, 12, None)];
+    let solver = network();
+    assert_eq!(solver.parametric_flow(0, 2), Ok(curve.clone()));
+    assert_eq!(solver.parametric_supply(&[1, 0, -1]), Ok(curve));
+
+    // nothing goes back from 2 to 0
+    assert_eq!(solver.parametric_flow(2, 0), Ok(vec![breakpoint(0, 0, None)]));
+}
+
+#[test]
+fn parametric_supply() {
+    // f(amount) is the cost of 2 * amount units from 0 to 2, interpolated at the integral amounts
+    let curve = vec![breakpoint(0, 0, Some(-2)), breakpoint(1, -2, Some(6)), breakpoint(2, 4, Some(8)), breakpoint(3, 12, None)];
+    assert_eq!(network().parametric_supply(&[2, 0, -2]), Ok(curve));
+
+    // a supply that is already shipped in the negative direction
+    let mut solver = network();
+    solver.add_supply(0, 3);
+    solver.add_supply(2, -3);
+    let curve = vec![breakpoint(0, 1, Some(-3)), breakpoint(1, -2, Some(1)), breakpoint(3, 0, None)];
+    assert_eq!(solver.parametric_supply(&[-1, 0, 1]), Ok(curve));
+}
+
+#[test]
+fn starts_from_the_optimal_flow() {
+    let mut solver = network();
+    solver.add_supply(0, 3);
+    solver.add_supply(2, -3);
+    assert_eq!(solver.solve(), Status::Optimal);
+    let flows: Vec<i64> = (0..4).map(|i| solver.get_directed_edge(EdgeId::new(i)).flow).collect();
+
+    let curve = vec![breakpoint(0, 1, Some(3)), breakpoint(2, 7, Some(5)), breakpoint(3, 12, None)];
+    assert_eq!(solver.parametric_flow(0, 2), Ok(curve));
+
+    // self is not modified
+    assert_eq!(solver.optimal_cost(), Some(1));
+    assert_eq!((0..4).map(|i| solver.get_directed_edge(EdgeId::new(i)).flow).collect::<Vec<i64>>(), flows);
+}
+
+#[test]
+fn returns_the_status_of_the_supplies() {
+    let mut solver = network();
+    solver.add_supply(0, 7);
+    solver.add_supply(2, -7);
+    assert_eq!(solver.parametric_flow(0, 2), Err(Status::Infeasible));
+    assert_eq!(solver.parametric_supply(&[1, 0, -1]), Err(Status::Infeasible));
+
+    // the capacities around node 0 do not fit in i128, and the sums of the direction saturate instead of overflowing
+    let mut solver: CostScalingPushRelabel<i128> = CostScalingPushRelabel::new(4);
+    solver.add_directed_edge(0, 1, 0, i128::MAX, 1);
+    solver.add_directed_edge(0, 2, 0, i128::MAX, 1);
+    assert_eq!(solver.parametric_supply(&[2, -1, -1, 0]), Err(Status::BadCostRange));
+
+    let mut solver: CostScalingPushRelabel<i128> = CostScalingPushRelabel::new(4);
+    solver.add_directed_edge(0, 2, 0, 1, 1);
+    let curve = vec![Breakpoint { amount: 0, cost: 0, marginal_cost: None }];
+    assert_eq!(solver.parametric_supply(&[i128::MAX, i128::MAX, -i128::MAX, -i128::MAX]), Ok(curve));
+}
+
+#[test]
+fn max_flow_with_budget() {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 2, 0, 2, -1);
+    problem.add_directed_edge(0, 1, 0, 3, 1);
+    problem.add_directed_edge(1, 2, 0, 3, 2);
+    problem.add_directed_edge(0, 2, 0, 1, 5);
+    let curve = network().parametric_flow(0, 2).unwrap();
+
+    for budget in -5..=15 {
+        // the largest amount whose cost on the curve is within the budget
+        let mut expected = None;
+        for amount in 0..=curve.last().unwrap().amount {
+            let k = curve.iter().rposition(|breakpoint| breakpoint.amount <= amount).unwrap();
+            let cost = curve[k].cost + curve[k].marginal_cost.unwrap_or(0) * (amount - curve[k].amount) as i128;
+            if cost <= budget {
+                expected = Some((amount, cost));
+            }
+        }
+
+        let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+        let (status, amount) = solver.solve_max_flow_with_budget(0, 2, budget);
+        match expected {
+            Some((expected_amount, cost)) => {
+                assert_eq!((status, amount), (Status::Optimal, expected_amount), "budget {}", budget);
+                assert_eq!(solver.optimal_cost(), Some(cost), "budget {}", budget);
+                let mut shifted = problem.clone();
+                shifted.add_supply(0, amount);
+                shifted.add_supply(2, -amount);
+                shifted.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+            }
+            None => {
+                assert_eq!((status, amount), (Status::Infeasible, 0), "budget {}", budget);
+                assert_eq!(solver.optimal_cost(), None);
+            }
+        }
+    }
+
+    // -2 for 2 units, or 4 units for 1 + 3 * 2
+    let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, -2), (Status::Optimal, 2));
+    let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, 4), (Status::Optimal, 4));
+}
+
+#[test]
+fn max_flow_with_budget_returns_the_status_of_the_supplies() {
+    let mut solver = network();
+    solver.add_supply(0, 7);
+    solver.add_supply(2, -7);
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, 100), (Status::Infeasible, 0));
+    assert_eq!(MinCostFlowSolver::status(&solver), Status::Infeasible);
+    assert_eq!(solver.optimal_cost(), None);
+}
+
+#[test]
+fn max_flow_with_budget_after_solve() {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 2, 0, 2, -1);
+    problem.add_directed_edge(0, 1, 0, 3, 1);
+    problem.add_directed_edge(1, 2, 0, 3, 2);
+    problem.add_directed_edge(0, 2, 0, 1, 5);
+
+    // the same as without the solve
+    let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<i64>>();
+    solver.solve();
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, 4), (Status::Optimal, 4));
+    assert_eq!(solver.optimal_cost(), Some(4));
+
+    // the 4 units are now supplies. the next unit costs 3, and the one after it 5
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, 9), (Status::Optimal, 1));
+    assert_eq!(solver.optimal_cost(), Some(7));
+    let mut shifted = problem.clone();
+    shifted.add_supply(0, 5);
+    shifted.add_supply(2, -5);
+    shifted.check_solution(&Solution::from_solver(&solver, &edge_ids)).unwrap();
+
+    // supplies added after the solve count
+    let (mut solver, _) = problem.build::<CostScalingPushRelabel<i64>>();
+    solver.solve();
+    solver.add_supply(0, 2);
+    solver.add_supply(2, -2);
+    assert_eq!(solver.solve_max_flow_with_budget(0, 2, 4), (Status::Optimal, 2));
+    assert_eq!(solver.optimal_cost(), Some(4));
+}
@@ -0,0 +1,385 @@
+use minimum_cost_flow_common::{Flow, MaxFlowProblem, MinCostFlowProblem, MinCostFlowSolver, Solution, Status};
+use std::collections::{HashMap, VecDeque};
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// DIMACS minimum cost flow format
+// c <comment>
+// p min <nodes> <arcs>
+// n <id> <supply>
+// a <from> <to> <lower> <upper> <cost>
+// nodes are numbered from 1 in the file and from 0 in the problem
+//
+// solution
+// s <cost>
+// f <from> <to> <flow>
+// d <id> <potential>   (optional. cost + potential[from] - potential[to] >= 0 on the residual network)
+//
+// DIMACS maximum flow format
+// p max <nodes> <arcs>
+// n <id> s
+// n <id> t
+// a <from> <to> <capacity>
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+fn parse_error<T>(line: usize, message: String) -> Result<T, Error> {
+    Err(Error::Parse { line, message })
+}
+
+// receives the lines of a file in the order they appear
+trait Builder<F: Flow> {
+    fn add_supply(&mut self, node: usize, supply: F);
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F);
+}
+
+impl<F: Flow> Builder<F> for MinCostFlowProblem<F> {
+    fn add_supply(&mut self, node: usize, supply: F) {
+        MinCostFlowProblem::add_supply(self, node, supply);
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) {
+        MinCostFlowProblem::add_directed_edge(self, from, to, lower, upper, cost);
+    }
+}
+
+struct SolverBuilder<S>(S);
+
+impl<F: Flow, S: MinCostFlowSolver<F>> Builder<F> for SolverBuilder<S> {
+    fn add_supply(&mut self, node: usize, supply: F) {
+        self.0.add_supply(node, supply);
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) {
+        self.0.add_directed_edge(from, to, lower, upper, cost);
+    }
+}
+
+pub fn read_problem<F: Flow, R: BufRead>(reader: R) -> Result<MinCostFlowProblem<F>, Error> {
+    parse(reader, MinCostFlowProblem::new)
+}
+
+// builds the solver while reading, without keeping a copy of the network
+// the i-th arc of the file becomes EdgeId::new(i)
+pub fn read_solver<F: Flow, S: MinCostFlowSolver<F>, R: BufRead>(reader: R) -> Result<S, Error> {
+    Ok(parse(reader, |num_of_nodes| SolverBuilder(S::new(num_of_nodes)))?.0)
+}
+
+pub fn write_problem<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "p min {} {}", problem.num_of_nodes, problem.num_of_edges())?;
+    for u in 0..problem.num_of_nodes {
+        if problem.supply[u] != F::zero() {
+            writeln!(writer, "n {} {}", u + 1, problem.supply[u])?;
+        }
+    }
+    for e in problem.edges.iter() {
+        writeln!(writer, "a {} {} {} {} {}", e.from + 1, e.to + 1, e.lower, e.upper, e.cost)?;
+    }
+    Ok(())
+}
+
+// writes the flows in the order of the arcs of the problem
+pub fn write_solution<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, solution: &Solution<F>, with_potentials: bool, mut writer: W) -> io::Result<()> {
+    assert_eq!(solution.status, Status::Optimal);
+
+    writeln!(writer, "s {}", solution.cost.unwrap())?;
+    for (e, flow) in problem.edges.iter().zip(solution.flows.iter()) {
+        writeln!(writer, "f {} {} {}", e.from + 1, e.to + 1, flow)?;
+    }
+    if with_potentials {
+        for (u, potential) in solution.potentials.iter().enumerate() {
+            writeln!(writer, "d {} {}", u + 1, potential)?;
+        }
+    }
+    Ok(())
+}
+
+// the flows of parallel arcs are assigned in the order of the arcs of the problem
+// arcs without an 'f' line have no flow
+// use MinCostFlowProblem::check_solution to validate the result
+pub fn read_solution<F: Flow, R: BufRead>(problem: &MinCostFlowProblem<F>, reader: R) -> Result<Solution<F>, Error> {
+    let num_of_nodes = problem.num_of_nodes;
+    let mut unassigned: HashMap<(usize, usize), VecDeque<usize>> = HashMap::new();
+    for (i, e) in problem.edges.iter().enumerate() {
+        unassigned.entry((e.from, e.to)).or_default().push_back(i);
+    }
+
+    let mut cost = None;
+    let mut flows = vec![F::zero(); problem.num_of_edges()];
+    let mut potentials = Vec::new();
+    let mut has_potential = vec![false; num_of_nodes];
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        line_number += 1;
+
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.is_empty() || v[0].starts_with('c') {
+            continue;
+        }
+
+        match v[0] {
+            "s" => {
+                if cost.is_some() {
+                    return parse_error(line_number, "duplicate 's' line".to_string());
+                }
+                check_len(&v, 2, line_number)?;
+                match v[1].parse::<i128>() {
+                    Ok(c) => cost = Some(c),
+                    Err(_) => return parse_error(line_number, format!("invalid cost '{}'", v[1])),
+                }
+            }
+            "f" => {
+                check_len(&v, 4, line_number)?;
+                let from = parse_node(v[1], num_of_nodes, line_number)?;
+                let to = parse_node(v[2], num_of_nodes, line_number)?;
+                let flow = parse_value(v[3], line_number)?;
+                match unassigned.get_mut(&(from, to)).and_then(|edges| edges.pop_front()) {
+                    Some(i) => flows[i] = flow,
+                    None => return parse_error(line_number, format!("no arc from {} to {} left in the problem", from + 1, to + 1)),
+                }
+            }
+            "d" => {
+                check_len(&v, 3, line_number)?;
+                let u = parse_node(v[1], num_of_nodes, line_number)?;
+                if has_potential[u] {
+                    return parse_error(line_number, format!("duplicate potential of node {}", u + 1));
+                }
+                if potentials.is_empty() {
+                    potentials = vec![F::zero(); num_of_nodes];
+                }
+                potentials[u] = parse_value(v[2], line_number)?;
+                has_potential[u] = true;
+            }
+            _ => return parse_error(line_number, format!("unknown line type '{}'", v[0])),
+        }
+    }
+
+    if cost.is_none() {
+        return parse_error(line_number, "missing 's' line".to_string());
+    }
+    if let Some(u) = (0..num_of_nodes).find(|&u| !potentials.is_empty() && !has_potential[u]) {
+        return parse_error(line_number, format!("missing potential of node {}", u + 1));
+    }
+
+    Ok(Solution { status: Status::Optimal, flows, potentials, cost })
+}
+
+pub fn read_max_flow_problem<F: Flow, R: BufRead>(reader: R) -> Result<MaxFlowProblem<F>, Error> {
+    let mut num_of_nodes = None;
+    let mut num_of_edges = 0;
+    let mut source = None;
+    let mut sink = None;
+    let mut edges = Vec::new();
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        line_number += 1;
+
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.is_empty() || v[0].starts_with('c') {
+            continue;
+        }
+
+        match v[0] {
+            "p" => {
+                if num_of_nodes.is_some() {
+                    return parse_error(line_number, "duplicate 'p' line".to_string());
+                }
+                check_len(&v, 4, line_number)?;
+                if v[1] != "max" {
+                    return parse_error(line_number, format!("unsupported problem type '{}'", v[1]));
+                }
+                num_of_nodes = Some(parse_number(v[2], line_number)?);
+                num_of_edges = parse_number(v[3], line_number)?;
+            }
+            "n" => {
+                let n = match num_of_nodes {
+                    Some(n) => n,
+                    None => return parse_error(line_number, "'n' line before 'p' line".to_string()),
+                };
+                check_len(&v, 3, line_number)?;
+                let node = parse_node(v[1], n, line_number)?;
+                let terminal = match v[2] {
+                    "s" => &mut source,
+                    "t" => &mut sink,
+                    _ => return parse_error(line_number, format!("node type must be 's' or 't' but is '{}'", v[2])),
+                };
+                if terminal.is_some() {
+                    return parse_error(line_number, format!("duplicate '{}' node", v[2]));
+                }
+                *terminal = Some(node);
+            }
+            "a" => {
+                let n = match num_of_nodes {
+                    Some(n) => n,
+                    None => return parse_error(line_number, "'a' line before 'p' line".to_string()),
+                };
+                check_len(&v, 4, line_number)?;
+                let from = parse_node(v[1], n, line_number)?;
+                let to = parse_node(v[2], n, line_number)?;
+                let upper: F = parse_value(v[3], line_number)?;
+                if upper < F::zero() {
+                    return parse_error(line_number, format!("capacity {} is negative", upper));
+                }
+                edges.push((from, to, upper));
+            }
+            _ => return parse_error(line_number, format!("unknown line type '{}'", v[0])),
+        }
+    }
+
+    let (num_of_nodes, source, sink) = match (num_of_nodes, source, sink) {
+        (None, _, _) => return parse_error(line_number, "missing 'p' line".to_string()),
+        (_, None, _) => return parse_error(line_number, "missing source ('n <id> s' line)".to_string()),
+        (_, _, None) => return parse_error(line_number, "missing sink ('n <id> t' line)".to_string()),
+        (Some(n), Some(s), Some(t)) => (n, s, t),
+    };
+    if source == sink {
+        return parse_error(line_number, "source and sink are the same node".to_string());
+    }
+    if edges.len() != num_of_edges {
+        return parse_error(line_number, format!("expected {} arcs but found {}", num_of_edges, edges.len()));
+    }
+
+    let mut problem = MaxFlowProblem::new(num_of_nodes, source, sink);
+    problem.edges = edges;
+    Ok(problem)
+}
+
+pub fn write_max_flow_problem<F: Flow, W: Write>(problem: &MaxFlowProblem<F>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "p max {} {}", problem.num_of_nodes, problem.edges.len())?;
+    writeln!(writer, "n {} s", problem.source + 1)?;
+    writeln!(writer, "n {} t", problem.sink + 1)?;
+    for &(from, to, upper) in problem.edges.iter() {
+        writeln!(writer, "a {} {} {}", from + 1, to + 1, upper)?;
+    }
+    Ok(())
+}
+
+fn parse<F: Flow, R: BufRead, B: Builder<F>>(reader: R, new: impl FnOnce(usize) -> B) -> Result<B, Error> {
+    let mut new = Some(new);
+    let mut builder = None;
+    let mut num_of_nodes = 0;
+    let mut num_of_edges = 0;
+    let mut has_supply = Vec::new();
+    let mut count = 0;
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        line_number += 1;
+
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.is_empty() || v[0].starts_with('c') {
+            continue;
+        }
+
+        match v[0] {
+            "p" => {
+                if builder.is_some() {
+                    return parse_error(line_number, "duplicate 'p' line".to_string());
+                }
+                check_len(&v, 4, line_number)?;
+                if v[1] != "min" {
+                    return parse_error(line_number, format!("unsupported problem type '{}'", v[1]));
+                }
+                num_of_nodes = parse_number(v[2], line_number)?;
+                num_of_edges = parse_number(v[3], line_number)?;
+                has_supply = vec![false; num_of_nodes];
+                builder = Some((new.take().unwrap())(num_of_nodes));
+            }
+            "n" => {
+                let builder = match builder.as_mut() {
+                    Some(builder) => builder,
+                    None => return parse_error(line_number, "'n' line before 'p' line".to_string()),
+                };
+                check_len(&v, 3, line_number)?;
+                let node = parse_node(v[1], num_of_nodes, line_number)?;
+                if has_supply[node] {
+                    return parse_error(line_number, format!("duplicate supply of node {}", node + 1));
+                }
+                let supply = parse_value(v[2], line_number)?;
+                builder.add_supply(node, supply);
+                has_supply[node] = true;
+            }
+            "a" => {
+                let builder = match builder.as_mut() {
+                    Some(builder) => builder,
+                    None => return parse_error(line_number, "'a' line before 'p' line".to_string()),
+                };
+                check_len(&v, 6, line_number)?;
+                let from = parse_node(v[1], num_of_nodes, line_number)?;
+                let to = parse_node(v[2], num_of_nodes, line_number)?;
+                let lower = parse_value(v[3], line_number)?;
+                let upper = parse_value(v[4], line_number)?;
+                let cost = parse_value(v[5], line_number)?;
+                if lower > upper {
+                    return parse_error(line_number, format!("lower bound {} is greater than capacity {}", lower, upper));
+                }
+                builder.add_directed_edge(from, to, lower, upper, cost);
+                count += 1;
+            }
+            _ => return parse_error(line_number, format!("unknown line type '{}'", v[0])),
+        }
+    }
+
+    match builder {
+        None => parse_error(line_number, "missing 'p' line".to_string()),
+        Some(_) if count != num_of_edges => parse_error(line_number, format!("expected {} arcs but found {}", num_of_edges, count)),
+        Some(builder) => Ok(builder),
+    }
+}
+
+fn check_len(v: &[&str], len: usize, line_number: usize) -> Result<(), Error> {
+    if v.len() != len {
+        return parse_error(line_number, format!("'{}' line must have {} fields but has {}", v[0], len, v.len()));
+    }
+    Ok(())
+}
+
+fn parse_number(token: &str, line_number: usize) -> Result<usize, Error> {
+    match token.parse() {
+        Ok(x) => Ok(x),
+        Err(_) => parse_error(line_number, format!("invalid number '{}'", token)),
+    }
+}
+
+// 1-indexed in the file
+fn parse_node(token: &str, num_of_nodes: usize, line_number: usize) -> Result<usize, Error> {
+    let id = parse_number(token, line_number)?;
+    if id == 0 || id > num_of_nodes {
+        return parse_error(line_number, format!("node {} is out of range [1, {}]", id, num_of_nodes));
+    }
+    Ok(id - 1)
+}
+
+fn parse_value<F: Flow>(token: &str, line_number: usize) -> Result<F, Error> {
+    match token.parse::<i128>().ok().and_then(F::from_i128) {
+        Some(x) => Ok(x),
+        None => parse_error(line_number, format!("invalid value '{}'", token)),
+    }
+}
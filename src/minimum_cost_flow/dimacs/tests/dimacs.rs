@@ -0,0 +1,113 @@
+use dimacs::{read_max_flow_problem, read_problem, read_solution, read_solver, write_problem, write_solution, Error};
+use minimum_cost_flow_common::{EdgeId, MinCostFlowProblem, Status};
+use network_simplex::NetworkSimplex;
+
+// the line and the message of a parse error
+fn parse_error<T>(result: Result<T, Error>) -> (usize, String) {
+    match result {
+        Err(Error::Parse { line, message }) => (line, message),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("parsed"),
+    }
+}
+
+fn read(text: &str) -> Result<MinCostFlowProblem<i64>, Error> {
+    read_problem(text.as_bytes())
+}
+
+#[test]
+fn reads_problem() {
+    let text = "c a comment\n\np min 3 2\nn 1 4\nn 3 -4\nc another\na 1 2 -1 5 3\na 2 3 0 4 -2\n";
+    let problem = read(text).unwrap();
+    assert_eq!(problem.num_of_nodes, 3);
+    assert_eq!(problem.supply, vec![4, 0, -4]);
+    assert_eq!(problem.edges.len(), 2);
+    assert_eq!((problem.edges[0].from, problem.edges[0].to, problem.edges[0].lower, problem.edges[0].upper, problem.edges[0].cost), (0, 1, -1, 5, 3));
+
+    let mut written = Vec::new();
+    write_problem(&problem, &mut written).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), "p min 3 2\nn 1 4\nn 3 -4\na 1 2 -1 5 3\na 2 3 0 4 -2\n");
+
+    let mut solver: NetworkSimplex<i64> = read_solver(text.as_bytes()).unwrap();
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(4 * 3 - 4 * 2));
+    assert_eq!(solver.get_directed_edge(EdgeId::new(1)).flow, 4);
+}
+
+#[test]
+fn rejects_bad_problem_lines() {
+    let cases = [
+        ("", 0, "missing 'p' line"),
+        ("c only a comment\n", 1, "missing 'p' line"),
+        ("p min 2 0\np min 2 0\n", 2, "duplicate 'p' line"),
+        ("p max 2 0\n", 1, "unsupported problem type 'max'"),
+        ("p min 2\n", 1, "'p' line must have 4 fields but has 3"),
+        ("p min -2 0\n", 1, "invalid number '-2'"),
+        ("n 1 3\np min 2 0\n", 1, "'n' line before 'p' line"),
+        ("a 1 2 0 1 0\np min 2 1\n", 1, "'a' line before 'p' line"),
+        ("p min 2 0\nn 1 3 4\n", 2, "'n' line must have 3 fields but has 4"),
+        ("p min 2 0\nn 0 3\n", 2, "node 0 is out of range [1, 2]"),
+        ("p min 2 0\nn 3 3\n", 2, "node 3 is out of range [1, 2]"),
+        ("p min 2 0\nn 1 x\n", 2, "invalid value 'x'"),
+        ("p min 2 0\nn 1 3\nn 2 -3\nn 1 1\n", 4, "duplicate supply of node 1"),
+        ("p min 2 1\na 1 2 0 1\n", 2, "'a' line must have 6 fields but has 5"),
+        ("p min 2 1\na 1 2 2 1 0\n", 2, "lower bound 2 is greater than capacity 1"),
+        ("p min 2 1\na 1 2 0 99999999999999999999 0\n", 2, "invalid value '99999999999999999999'"),
+        ("p min 2 2\na 1 2 0 1 0\n", 2, "expected 2 arcs but found 1"),
+        ("p min 2 0\nx 1\n", 2, "unknown line type 'x'"),
+    ];
+    for &(text, line, message) in cases.iter() {
+        assert_eq!(parse_error(read(text)), (line, message.to_string()), "{:?}", text);
+    }
+
+    // the value must fit in the flow type
+    assert_eq!(parse_error(read_problem::<i32, _>("p min 2 0\nn 1 3000000000\n".as_bytes())), (2, "invalid value '3000000000'".to_string()));
+    let result: Result<NetworkSimplex<i64>, Error> = read_solver("p min 1 0\nn 1 1\nn 1 1\n".as_bytes());
+    assert_eq!(parse_error(result), (3, "duplicate supply of node 1".to_string()));
+}
+
+#[test]
+fn rejects_bad_max_flow_lines() {
+    let cases = [
+        ("", 0, "missing 'p' line"),
+        ("p max 2 0\np max 2 0\n", 2, "duplicate 'p' line"),
+        ("p min 2 0\n", 1, "unsupported problem type 'min'"),
+        ("n 1 s\n", 1, "'n' line before 'p' line"),
+        ("p max 2 0\nn 1 x\n", 2, "node type must be 's' or 't' but is 'x'"),
+        ("p max 2 0\nn 1 s\nn 2 s\n", 3, "duplicate 's' node"),
+        ("p max 2 0\nn 1 t\nn 2 t\n", 3, "duplicate 't' node"),
+        ("p max 2 1\nn 1 s\nn 2 t\na 1 2 -1\n", 4, "capacity -1 is negative"),
+        ("p max 2 0\nn 2 t\n", 2, "missing source ('n <id> s' line)"),
+        ("p max 2 0\nn 1 s\n", 2, "missing sink ('n <id> t' line)"),
+        ("p max 2 0\nn 1 s\nn 1 t\n", 3, "source and sink are the same node"),
+        ("p max 2 1\nn 1 s\nn 2 t\n", 3, "expected 1 arcs but found 0"),
+    ];
+    for &(text, line, message) in cases.iter() {
+        assert_eq!(parse_error(read_max_flow_problem::<i64, _>(text.as_bytes())), (line, message.to_string()), "{:?}", text);
+    }
+
+    let problem = read_max_flow_problem::<i64, _>("p max 3 2\nn 3 t\nn 1 s\na 1 2 5\na 2 3 4\n".as_bytes()).unwrap();
+    assert_eq!((problem.source, problem.sink), (0, 2));
+    assert_eq!(problem.edges, vec![(0, 1, 5), (1, 2, 4)]);
+}
+
+#[test]
+fn reads_and_writes_solution() {
+    let problem = read("p min 2 2\nn 1 3\nn 2 -3\na 1 2 0 2 1\na 1 2 0 2 2\n").unwrap();
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    let mut written = Vec::new();
+    write_solution(&problem, &solution, true, &mut written).unwrap();
+    let read_back = read_solution(&problem, written.as_slice()).unwrap();
+    assert_eq!(read_back, solution);
+    problem.check_solution(&read_back).unwrap();
+
+    // parallel arcs are assigned in order, and arcs without an 'f' line have no flow
+    let read_back = read_solution(&problem, "s 4\nf 1 2 2\nf 1 2 1\n".as_bytes()).unwrap();
+    assert_eq!(read_back.flows, vec![2, 1]);
+    assert!(read_back.potentials.is_empty());
+
+    let cases = [("", 0, "missing 's' line"), ("s 4\ns 4\n", 2, "duplicate 's' line"), ("s x\n", 1, "invalid cost 'x'"), ("s 4\nf 1 2\n", 2, "'f' line must have 4 fields but has 3"), ("s 4\nf 2 1 1\n", 2, "no arc from 2 to 1 left in the problem"), ("s 4\nf 1 2 1\nf 1 2 1\nf 1 2 1\n", 4, "no arc from 1 to 2 left in the problem"), ("s 4\nd 1 0\nd 1 0\n", 3, "duplicate potential of node 1"), ("s 4\nd 2 0\n", 2, "missing potential of node 1"), ("s 4\ne 1\n", 2, "unknown line type 'e'")];
+    for &(text, line, message) in cases.iter() {
+        assert_eq!(parse_error(read_solution(&problem, text.as_bytes())), (line, message.to_string()), "{:?}", text);
+    }
+}
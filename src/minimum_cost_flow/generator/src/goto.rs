@@ -0,0 +1,53 @@
+use crate::random::Random;
+use crate::{check, ParameterError};
+use minimum_cost_flow_common::MinCostFlowProblem;
+
+// the GOTO family (Goldberg): grid on torus networks with long cheap arcs, which are hard for cost scaling
+// the nodes form a width x height torus, numbered row by row. every node has an arc to its right
+// and lower neighbor (wrapping around) with capacity max_capacity and cost max_cost.
+// the other degree - 2 arcs of a node are cheap and thin: the k-th one (from 0) jumps up to 2^(k + 1) rows and
+// columns ahead, with a capacity in [1, max_capacity] and a cost in [0, max_cost * (k + 1) / (degree - 1)]
+// node 0 supplies 2 * max_capacity to the node in the middle of the torus. two arc-disjoint ring
+// paths carry it, so every instance is feasible
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GotoParameters {
+    pub width: usize,
+    pub height: usize,
+    pub degree: usize, // arcs out of each node
+    pub max_capacity: i64,
+    pub max_cost: i64,
+}
+
+pub fn goto(seed: i64, parameters: &GotoParameters) -> Result<MinCostFlowProblem<i64>, ParameterError> {
+    let p = parameters;
+    check(seed > 0 && seed < 2147483647, "seed must be in [1, 2^31 - 2]")?;
+    check(p.width >= 2 && p.height >= 2, "width and height must be at least 2")?;
+    check(p.degree >= 2, "degree must be at least 2")?;
+    check(p.max_capacity >= 1, "max_capacity must be positive")?;
+    check(p.max_cost >= 0, "max_cost must be non-negative")?;
+
+    let mut random = Random::new(seed);
+    let (w, h) = (p.width, p.height);
+    let node = |r: usize, c: usize| (r % h) * w + c % w;
+    let mut problem = MinCostFlowProblem::new(w * h);
+
+    for r in 0..h {
+        for c in 0..w {
+            let u = node(r, c);
+            problem.add_directed_edge(u, node(r, c + 1), 0, p.max_capacity, p.max_cost);
+            problem.add_directed_edge(u, node(r + 1, c), 0, p.max_capacity, p.max_cost);
+            for k in 0..p.degree - 2 {
+                let range = 1i64 << (k + 1).min(30);
+                let dr = random.next(0, range.min(h as i64 - 1)) as usize;
+                let dc = random.next(if dr == 0 { 1 } else { 0 }, range.min(w as i64 - 1)) as usize;
+                let capacity = random.next(1, p.max_capacity);
+                let cost = random.next(0, p.max_cost * (k as i64 + 1) / (p.degree as i64 - 1));
+                problem.add_directed_edge(u, node(r + dr, c + dc), 0, capacity, cost);
+            }
+        }
+    }
+
+    problem.add_supply(0, 2 * p.max_capacity);
+    problem.add_supply(node(h / 2, w / 2), -2 * p.max_capacity);
+    Ok(problem)
+}
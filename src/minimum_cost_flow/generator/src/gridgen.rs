@@ -0,0 +1,80 @@
+use crate::random::Random;
+use crate::{check, distribute_supply, ParameterError};
+use minimum_cost_flow_common::MinCostFlowProblem;
+
+// the GRIDGEN family (Lee and Orlin): grid networks with random extra arcs
+// the nodes form a width x height grid, numbered row by row. neighbors are joined by arcs in both
+// directions with capacity `supply`, so every instance is feasible. the remaining arcs join random
+// pairs of nodes with capacities in [min_capacity, max_capacity]. all costs are in [min_cost, max_cost]
+// the supply is split evenly among random source nodes, the demand among random sink nodes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridgenParameters {
+    pub width: usize,
+    pub height: usize,
+    pub arcs: usize, // total number of arcs, at least the number of grid arcs
+    pub sources: usize,
+    pub sinks: usize,
+    pub supply: i64,
+    pub min_cost: i64,
+    pub max_cost: i64,
+    pub min_capacity: i64,
+    pub max_capacity: i64,
+}
+
+impl GridgenParameters {
+    pub fn num_of_nodes(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn num_of_grid_arcs(&self) -> usize {
+        2 * ((self.width - 1) * self.height + self.width * (self.height - 1))
+    }
+}
+
+pub fn gridgen(seed: i64, parameters: &GridgenParameters) -> Result<MinCostFlowProblem<i64>, ParameterError> {
+    let p = parameters;
+    check(seed > 0 && seed < 2147483647, "seed must be in [1, 2^31 - 2]")?;
+    check(p.width >= 1 && p.height >= 1 && p.num_of_nodes() >= 2, "the grid must have at least 2 nodes")?;
+    check(p.arcs >= p.num_of_grid_arcs(), "arcs must be at least the number of grid arcs")?;
+    check(p.sources >= 1 && p.sinks >= 1 && p.sources + p.sinks <= p.num_of_nodes(), "sources and sinks must be positive and at most the number of nodes")?;
+    check(p.supply >= 0, "supply must be non-negative")?;
+    check(p.min_cost <= p.max_cost, "min_cost must be at most max_cost")?;
+    check(0 <= p.min_capacity && p.min_capacity <= p.max_capacity, "capacities must satisfy 0 <= min_capacity <= max_capacity")?;
+
+    let mut random = Random::new(seed);
+    let n = p.num_of_nodes();
+    let mut problem = MinCostFlowProblem::new(n);
+
+    for r in 0..p.height {
+        for c in 0..p.width {
+            let u = r * p.width + c;
+            let mut neighbors = Vec::new();
+            if c + 1 < p.width {
+                neighbors.push(u + 1);
+            }
+            if r + 1 < p.height {
+                neighbors.push(u + p.width);
+            }
+            for v in neighbors {
+                for &(from, to) in [(u, v), (v, u)].iter() {
+                    let cost = random.next(p.min_cost, p.max_cost);
+                    problem.add_directed_edge(from, to, 0, p.supply, cost);
+                }
+            }
+        }
+    }
+
+    while problem.num_of_edges() < p.arcs {
+        let from = random.next(0, n as i64 - 1) as usize;
+        let to = random.next(0, n as i64 - 2) as usize;
+        let to = if to >= from { to + 1 } else { to };
+        let capacity = random.next(p.min_capacity, p.max_capacity);
+        let cost = random.next(p.min_cost, p.max_cost);
+        problem.add_directed_edge(from, to, 0, capacity, cost);
+    }
+
+    let terminals = random.sample(n, p.sources + p.sinks);
+    distribute_supply(&mut problem, &terminals[..p.sources], p.supply);
+    distribute_supply(&mut problem, &terminals[p.sources..], -p.supply);
+    Ok(problem)
+}
@@ -0,0 +1,43 @@
+#![allow(clippy::needless_range_loop)]
+use minimum_cost_flow_common::MinCostFlowProblem;
+use std::error;
+use std::fmt;
+
+// random instance generators
+// netgen is a port of the original program. gridgen and goto generate the GRIDGEN and GOTO families
+// from their published descriptions, not from the C code: they have the same structure, but they do not
+// reproduce the networks of gridgen.c and goto.c for a seed. road generates road-like networks
+pub mod goto;
+pub mod gridgen;
+pub mod netgen;
+mod random;
+pub mod road;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterError {
+    pub message: String,
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid parameters: {}", self.message)
+    }
+}
+
+impl error::Error for ParameterError {}
+
+pub(crate) fn check(condition: bool, message: &str) -> Result<(), ParameterError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(ParameterError { message: message.to_string() })
+    }
+}
+
+// splits supply evenly among the nodes. the first node gets the remainder
+pub(crate) fn distribute_supply(problem: &mut MinCostFlowProblem<i64>, nodes: &[usize], supply: i64) {
+    let k = nodes.len() as i64;
+    for (i, &u) in nodes.iter().enumerate() {
+        problem.add_supply(u, supply / k + if i == 0 { supply % k } else { 0 });
+    }
+}
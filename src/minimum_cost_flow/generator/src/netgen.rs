@@ -0,0 +1,500 @@
+use crate::random::Random;
+use minimum_cost_flow_common::MinCostFlowProblem;
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// port of netgen.c (the DIMACS version of NETGEN by Norbert Schlenker)
+// Klingman, Napier and Stutz, "NETGEN: A Program for Generating Large Scale Capacitated Assignment,
+// Transportation, and Minimum Cost Flow Network Problems", Management Science 20 (1974)
+//
+// the random numbers, the index lists and the order of the calls follow the C code, so that
+// the same seed and parameters give the same network. the C longs are i64
+// the supplies and arcs are checked against one instance of the C code (netgen-2 of the networkx
+// tests), but only as a set of arcs. write_dimacs follows the printf calls of netgen.c, but its header
+// and arc order have not been compared with the output of the C program
+//
+// assignment problem if sources + sinks == nodes, there are no transshipment nodes, sources == sinks and supply == sources
+// maximum flow problem if not an assignment problem and min_cost == max_cost == 1
+// minimum cost flow problem otherwise
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NetgenParameters {
+    pub nodes: i64,
+    pub sources: i64, // including transshipment sources
+    pub sinks: i64,   // including transshipment sinks
+    pub density: i64, // number of requested arcs
+    pub min_cost: i64,
+    pub max_cost: i64,
+    pub supply: i64, // total supply
+    pub transshipment_sources: i64,
+    pub transshipment_sinks: i64,
+    pub high_cost: i64,   // percent of skeleton arcs with the maximum cost
+    pub capacitated: i64, // percent of arcs with a capacity
+    pub min_capacity: i64,
+    pub max_capacity: i64,
+}
+
+impl NetgenParameters {
+    // the 13 parameters in the order of the NETGEN input
+    pub fn from_slice(parms: &[i64; 13]) -> Self {
+        NetgenParameters {
+            nodes: parms[0],
+            sources: parms[1],
+            sinks: parms[2],
+            density: parms[3],
+            min_cost: parms[4],
+            max_cost: parms[5],
+            supply: parms[6],
+            transshipment_sources: parms[7],
+            transshipment_sinks: parms[8],
+            high_cost: parms[9],
+            capacitated: parms[10],
+            min_capacity: parms[11],
+            max_capacity: parms[12],
+        }
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        let sources = self.sources - self.transshipment_sources;
+        let sinks = self.sinks - self.transshipment_sinks;
+        sources + sinks == self.nodes && sources == sinks && self.sources == self.supply
+    }
+
+    pub fn is_max_flow(&self) -> bool {
+        !self.is_assignment() && self.min_cost == 1 && self.max_cost == 1
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NetgenError {
+    BadSeed,
+    BadParameters,
+}
+
+impl fmt::Display for NetgenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetgenError::BadSeed => write!(f, "NETGEN requires a positive random seed"),
+            NetgenError::BadParameters => write!(f, "Inconsistent parameter settings - check the input"),
+        }
+    }
+}
+
+impl error::Error for NetgenError {}
+
+// arcs are (from, to, cost, capacity) and nodes are numbered from 1 as in the C code
+// supply[u] is the supply of node u + 1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Netgen {
+    pub seed: i64,
+    pub parameters: NetgenParameters,
+    pub supply: Vec<i64>,
+    pub arcs: Vec<(usize, usize, i64, i64)>,
+}
+
+impl Netgen {
+    // all arcs have lower bound 0
+    pub fn to_problem(&self) -> MinCostFlowProblem<i64> {
+        let mut problem = MinCostFlowProblem::new(self.supply.len());
+        for &(from, to, cost, capacity) in self.arcs.iter() {
+            problem.add_directed_edge(from - 1, to - 1, 0, capacity, cost);
+        }
+        for (u, &b) in self.supply.iter().enumerate() {
+            if b != 0 {
+                problem.add_supply(u, b);
+            }
+        }
+        problem
+    }
+
+    // the output of the DIMACS netgen program for one problem
+    pub fn write_dimacs<W: Write>(&self, problem: i64, mut writer: W) -> io::Result<()> {
+        let p = &self.parameters;
+        writeln!(writer, "c NETGEN flow network generator (C version)")?;
+        writeln!(writer, "c  Problem {:2} input parameters", problem)?;
+        writeln!(writer, "c  ---------------------------")?;
+        writeln!(writer, "c   Random seed:          {:10}", self.seed)?;
+        writeln!(writer, "c   Number of nodes:      {:10}", p.nodes)?;
+        writeln!(writer, "c   Source nodes:         {:10}", p.sources)?;
+        writeln!(writer, "c   Sink nodes:           {:10}", p.sinks)?;
+        writeln!(writer, "c   Number of arcs:       {:10}", p.density)?;
+        writeln!(writer, "c   Minimum arc cost:     {:10}", p.min_cost)?;
+        writeln!(writer, "c   Maximum arc cost:     {:10}", p.max_cost)?;
+        writeln!(writer, "c   Total supply:         {:10}", p.supply)?;
+        writeln!(writer, "c   Transshipment -")?;
+        writeln!(writer, "c     Sources:            {:10}", p.transshipment_sources)?;
+        writeln!(writer, "c     Sinks:              {:10}", p.transshipment_sinks)?;
+        writeln!(writer, "c   Skeleton arcs -")?;
+        writeln!(writer, "c     With max cost:      {:10}%", p.high_cost)?;
+        writeln!(writer, "c     Capacitated:        {:10}%", p.capacitated)?;
+        writeln!(writer, "c   Minimum arc capacity: {:10}", p.min_capacity)?;
+        writeln!(writer, "c   Maximum arc capacity: {:10}", p.max_capacity)?;
+
+        if p.is_assignment() {
+            writeln!(writer, "c\nc  *** Assignment ***\nc")?;
+            writeln!(writer, "p asn {} {}", p.nodes, self.arcs.len())?;
+            for (u, &b) in self.supply.iter().enumerate() {
+                if b > 0 {
+                    writeln!(writer, "n {}", u + 1)?;
+                }
+            }
+            for &(from, to, cost, _) in self.arcs.iter() {
+                writeln!(writer, "a {} {} {}", from, to, cost)?;
+            }
+        } else if p.is_max_flow() {
+            writeln!(writer, "c\nc  *** Maximum flow ***\nc")?;
+            writeln!(writer, "p max {} {}", p.nodes, self.arcs.len())?;
+            for (u, &b) in self.supply.iter().enumerate() {
+                if b > 0 {
+                    writeln!(writer, "n {} s", u + 1)?;
+                } else if b < 0 {
+                    writeln!(writer, "n {} t", u + 1)?;
+                }
+            }
+            for &(from, to, _, capacity) in self.arcs.iter() {
+                writeln!(writer, "a {} {} {}", from, to, capacity)?;
+            }
+        } else {
+            writeln!(writer, "c\nc  *** Minimum cost flow ***\nc")?;
+            writeln!(writer, "p min {} {}", p.nodes, self.arcs.len())?;
+            for (u, &b) in self.supply.iter().enumerate() {
+                if b != 0 {
+                    writeln!(writer, "n {} {}", u + 1, b)?;
+                }
+            }
+            for &(from, to, cost, capacity) in self.arcs.iter() {
+                writeln!(writer, "a {} {} 0 {} {}", from, to, capacity, cost)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// reads the input of the netgen program: `seed problem nodes sources sinks density min_cost max_cost supply
+// transshipment_sources transshipment_sinks high_cost capacitated min_capacity max_capacity`, repeated.
+// reading stops at a seed <= 0, at the end of the input or at a token that is not a number, as in the C code
+pub fn read_parameters<R: BufRead>(reader: R) -> io::Result<Vec<(i64, i64, NetgenParameters)>> {
+    let mut tokens = Vec::new();
+    for line in reader.lines() {
+        tokens.extend(line?.split_whitespace().map(|t| t.to_string()));
+    }
+
+    let mut result = Vec::new();
+    let mut numbers = tokens.iter().map(|t| t.parse::<i64>().ok());
+    'read: loop {
+        let mut values = [0; 15];
+        for (k, value) in values.iter_mut().enumerate() {
+            match numbers.next() {
+                Some(Some(x)) if k > 0 || x > 0 => *value = x,
+                _ => break 'read,
+            }
+        }
+        let mut parms = [0; 13];
+        parms.copy_from_slice(&values[2..]);
+        result.push((values[0], values[1], NetgenParameters::from_slice(&parms)));
+    }
+    Ok(result)
+}
+
+pub fn netgen(seed: i64, parameters: &NetgenParameters) -> Result<Netgen, NetgenError> {
+    let p = parameters;
+    if seed <= 0 {
+        return Err(NetgenError::BadSeed);
+    }
+    if p.nodes <= 0 || p.nodes > p.density || p.sources <= 0 || p.sinks <= 0 || p.sources + p.sinks > p.nodes || p.min_cost > p.max_cost || p.supply < p.sources || p.transshipment_sources > p.sources || p.transshipment_sinks > p.sinks || p.high_cost < 0 || p.high_cost > 100 || p.capacitated < 0 || p.capacitated > 100 || p.min_capacity > p.max_capacity {
+        return Err(NetgenError::BadParameters);
+    }
+
+    let mut generator = Generator { p: *p, random: Random::new(seed), nodes_left: p.nodes - p.sinks + p.transshipment_sinks, b: vec![0; p.nodes as usize], arcs: Vec::new() };
+    if p.is_assignment() {
+        generator.create_assignment();
+    } else {
+        generator.create_network();
+    }
+    Ok(Netgen { seed, parameters: *p, supply: generator.b, arcs: generator.arcs })
+}
+
+// an ascending list of the integers [from, to] (empty if from > to)
+// choose removes the integer at a position, remove removes an integer
+// pseudo_size is decreased by both, and by removes of integers that are not in the list, as in index.c.
+// NETGEN picks positions up to pseudo_size, and positions beyond the actual size choose nothing
+struct IndexList {
+    from: usize,
+    tree: Vec<usize>, // Fenwick tree of the remaining integers
+    present: Vec<bool>,
+    size: usize,
+    pseudo_size: i64,
+}
+
+impl IndexList {
+    fn new(from: i64, to: i64) -> Self {
+        let n = if from <= to { (to - from + 1) as usize } else { 0 };
+        let mut tree = vec![0; n + 1];
+        for i in 1..=n {
+            tree[i] += 1;
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                tree[j] += tree[i];
+            }
+        }
+        IndexList { from: from.max(0) as usize, tree, present: vec![true; n], size: n, pseudo_size: n as i64 }
+    }
+
+    // returns 0 if the position is invalid
+    fn choose(&mut self, position: i64) -> usize {
+        if position < 1 || position as usize > self.size {
+            return 0;
+        }
+        let n = self.present.len();
+        let mut rest = position as usize;
+        let mut i = 0;
+        let mut step = n.next_power_of_two();
+        while step > 0 {
+            if i + step <= n && self.tree[i + step] < rest {
+                i += step;
+                rest -= self.tree[i];
+            }
+            step /= 2;
+        }
+        self.delete(i);
+        self.pseudo_size -= 1;
+        self.from + i
+    }
+
+    fn remove(&mut self, index: usize) {
+        if index >= self.from && index - self.from < self.present.len() && self.present[index - self.from] {
+            self.delete(index - self.from);
+        }
+        self.pseudo_size -= 1;
+    }
+
+    fn delete(&mut self, i: usize) {
+        self.present[i] = false;
+        self.size -= 1;
+        let mut j = i + 1;
+        while j < self.tree.len() {
+            self.tree[j] -= 1;
+            j += j & j.wrapping_neg();
+        }
+    }
+}
+
+struct Generator {
+    p: NetgenParameters,
+    random: Random,
+    nodes_left: i64,
+    b: Vec<i64>,
+    arcs: Vec<(usize, usize, i64, i64)>,
+}
+
+impl Generator {
+    fn create_supply(&mut self, sources: i64, supply: i64) {
+        let supply_per_source = supply / sources;
+        for i in 0..sources as usize {
+            let partial_supply = self.random.next(1, supply_per_source);
+            self.b[i] += partial_supply;
+            let j = self.random.next(0, sources - 1) as usize;
+            self.b[j] += supply_per_source - partial_supply;
+        }
+        let j = self.random.next(0, sources - 1) as usize;
+        self.b[j] += supply % sources;
+    }
+
+    fn create_assignment(&mut self) {
+        let p = self.p;
+        for u in 0..p.nodes as usize {
+            self.b[u] = if (u as i64) < p.nodes / 2 { 1 } else { -1 };
+        }
+
+        let mut skeleton = IndexList::new(p.sources + 1, p.nodes);
+        for source in 1..=(p.nodes / 2) as usize {
+            let position = self.random.next(1, skeleton.size as i64);
+            let index = skeleton.choose(position);
+            let cost = self.random.next(p.min_cost, p.max_cost);
+            self.arcs.push((source, index, cost, 1));
+            let mut handle = IndexList::new(p.sources + 1, p.nodes);
+            handle.remove(index);
+            self.pick_head(&mut handle, source);
+        }
+    }
+
+    fn create_network(&mut self) {
+        let p = self.p;
+        let n = p.nodes as usize;
+        let sources = p.sources as usize;
+        self.create_supply(p.sources, p.supply);
+
+        // form the skeleton chains. 60% of the transshipment nodes are divided evenly among the sources,
+        // the rest are chained onto random sources
+        let mut pred = vec![0; n + 1];
+        for i in 1..=sources {
+            pred[i] = i;
+        }
+        let mut handle = IndexList::new(p.sources + 1, p.nodes - p.sinks);
+        let transshipment = p.nodes - p.sources - p.sinks;
+        let mut source = 1;
+        let mut i = transshipment;
+        while i > (4 * transshipment + 9) / 10 {
+            let position = self.random.next(1, handle.size as i64);
+            let node = handle.choose(position);
+            pred[node] = pred[source];
+            pred[source] = node;
+            source += 1;
+            if source > sources {
+                source = 1;
+            }
+            i -= 1;
+        }
+        while i > 0 {
+            let position = self.random.next(1, handle.size as i64);
+            let node = handle.choose(position);
+            let source = self.random.next(1, p.sources) as usize;
+            pred[node] = pred[source];
+            pred[source] = node;
+            i -= 1;
+        }
+
+        // hook each source chain up to its sinks
+        let mut head = vec![0; n + 2];
+        let mut tail = vec![0; n + 2];
+        for source in 1..=sources {
+            let mut sort_count = 0;
+            let mut node = pred[source];
+            while node != source {
+                sort_count += 1;
+                head[sort_count] = node;
+                node = pred[node];
+                tail[sort_count] = node;
+            }
+
+            let sinks_per_source = if transshipment == 0 { p.sinks / p.sources + 1 } else { 2 * sort_count as i64 * p.sinks / transshipment };
+            let sinks_per_source = i64::max(2, i64::min(sinks_per_source, p.sinks)) as usize;
+            // sinks are numbered from 0 here
+            let mut sinks = Vec::with_capacity(sinks_per_source);
+            let mut handle = IndexList::new(p.nodes - p.sinks, p.nodes - 1);
+            for _ in 0..sinks_per_source {
+                let position = self.random.next(1, handle.size as i64);
+                sinks.push(handle.choose(position));
+            }
+            if source == sources {
+                while handle.size > 0 {
+                    let j = handle.choose(1);
+                    if self.b[j] == 0 {
+                        sinks.push(j);
+                    }
+                }
+            }
+            let sinks_per_source = sinks.len();
+
+            let chain_length = sort_count;
+            let supply_per_sink = self.b[source - 1] / sinks_per_source as i64;
+            let mut k = pred[source];
+            for i in 0..sinks_per_source {
+                sort_count += 1;
+                let partial_supply = self.random.next(1, supply_per_sink);
+                let j = self.random.next(0, sinks_per_source as i64 - 1) as usize;
+                tail[sort_count] = k;
+                head[sort_count] = sinks[i] + 1;
+                self.b[sinks[i]] -= partial_supply;
+                self.b[sinks[j]] -= supply_per_sink - partial_supply;
+                k = source;
+                for _ in 0..self.random.next(1, chain_length as i64) {
+                    k = pred[k];
+                }
+            }
+            self.b[sinks[0]] -= self.b[source - 1] % sinks_per_source as i64;
+
+            sort_skeleton(&mut tail, &mut head, sort_count);
+            tail[sort_count + 1] = 0;
+            let mut i = 1;
+            while i <= sort_count {
+                let mut handle = IndexList::new(p.sources - p.transshipment_sources + 1, p.nodes);
+                handle.remove(tail[i]);
+                let it = tail[i];
+                while it == tail[i] {
+                    handle.remove(head[i]);
+                    let mut capacity = p.supply;
+                    if self.random.next(1, 100) <= p.capacitated {
+                        capacity = i64::max(self.b[source - 1], p.min_capacity);
+                    }
+                    let mut cost = p.max_cost;
+                    if self.random.next(1, 100) > p.high_cost {
+                        cost = self.random.next(p.min_cost, p.max_cost);
+                    }
+                    self.arcs.push((it, head[i], cost, capacity));
+                    i += 1;
+                }
+                self.pick_head(&mut handle, it);
+            }
+        }
+
+        // the transshipment sinks get arcs too
+        for i in (p.nodes - p.sinks + 1)..=(p.nodes - p.sinks + p.transshipment_sinks) {
+            let mut handle = IndexList::new(p.sources - p.transshipment_sources + 1, p.nodes);
+            handle.remove(i as usize);
+            self.pick_head(&mut handle, i as usize);
+        }
+    }
+
+    // adds random arcs out of desired_tail so that the arc count reaches density
+    fn pick_head(&mut self, handle: &mut IndexList, desired_tail: usize) {
+        let p = self.p;
+        let non_sources = p.nodes - p.sources + p.transshipment_sources;
+        let remaining_arcs = p.density - self.arcs.len() as i64;
+
+        self.nodes_left -= 1;
+        if 2 * self.nodes_left >= remaining_arcs {
+            return;
+        }
+
+        let mut limit;
+        if (remaining_arcs + non_sources - handle.pseudo_size - 1) / (self.nodes_left + 1) >= non_sources - 1 {
+            limit = non_sources;
+        } else {
+            let upper_bound = 2 * (remaining_arcs / (self.nodes_left + 1) - 1);
+            loop {
+                limit = self.random.next(1, upper_bound);
+                if self.nodes_left == 0 {
+                    limit = remaining_arcs;
+                }
+                if self.nodes_left * (non_sources - 1) >= remaining_arcs - limit {
+                    break;
+                }
+            }
+        }
+
+        while limit > 0 {
+            let position = self.random.next(1, handle.pseudo_size);
+            let index = handle.choose(position);
+            let mut capacity = p.supply;
+            if self.random.next(1, 100) <= p.capacitated {
+                capacity = self.random.next(p.min_capacity, p.max_capacity);
+            }
+            if index != 0 {
+                let cost = self.random.next(p.min_cost, p.max_cost);
+                self.arcs.push((desired_tail, index, cost, capacity));
+            }
+            limit -= 1;
+        }
+    }
+}
+
+// Shell sort of the skeleton arcs by tail. positions start at 1
+fn sort_skeleton(tail: &mut [usize], head: &mut [usize], sort_count: usize) {
+    let mut m = sort_count;
+    loop {
+        m /= 2;
+        if m == 0 {
+            break;
+        }
+        for j in 1..=sort_count - m {
+            let mut i = j as i64;
+            while i >= 1 && tail[i as usize] > tail[i as usize + m] {
+                tail.swap(i as usize, i as usize + m);
+                head.swap(i as usize, i as usize + m);
+                i -= m as i64;
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// the "minimal standard" generator of Park and Miller with Schrage's method, as in netgen.c
+// the seed must be in [1, 2^31 - 2]
+pub(crate) struct Random {
+    seed: i64,
+}
+
+impl Random {
+    pub(crate) fn new(seed: i64) -> Self {
+        Random { seed }
+    }
+
+    // a random integer in [a, b]
+    pub(crate) fn next(&mut self, a: i64, b: i64) -> i64 {
+        let hi = self.seed / 127773;
+        let lo = self.seed % 127773;
+        self.seed = 16807 * lo - 2836 * hi;
+        if self.seed <= 0 {
+            self.seed += 2147483647;
+        }
+        // the C code divides by zero for an empty range
+        if b < a {
+            return a;
+        }
+        a + self.seed % (b - a + 1)
+    }
+
+    // k distinct integers of [0, n) in random order
+    pub(crate) fn sample(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let mut v: Vec<usize> = (0..n).collect();
+        for i in 0..k {
+            let j = self.next(i as i64, n as i64 - 1) as usize;
+            v.swap(i, j);
+        }
+        v.truncate(k);
+        v
+    }
+}
@@ -0,0 +1,86 @@
+use crate::random::Random;
+use crate::{check, ParameterError};
+use minimum_cost_flow_common::MinCostFlowProblem;
+
+// road-like networks
+// the nodes are the crossings of a width x height grid with 100 units between neighbors, each moved by up
+// to 30 units in x and y. neighbors are joined by local roads in both directions. every row and column
+// that is a multiple of highway_spacing is a highway: its roads are twice as fast, and consecutive
+// highway crossings are also joined by express arcs that are three times as fast
+// the cost of an arc is its travel time: the euclidean length divided by the speed, times a random
+// congestion factor in [1, 1.5]. local roads have capacity `capacity`, highways and express arcs
+// highway_spacing times as much, so the instance may be infeasible if the capacity is small
+// `pairs` random origin and destination nodes each ship supply_per_pair
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RoadParameters {
+    pub width: usize,
+    pub height: usize,
+    pub highway_spacing: usize,
+    pub capacity: i64,
+    pub pairs: usize,
+    pub supply_per_pair: i64,
+}
+
+pub fn road(seed: i64, parameters: &RoadParameters) -> Result<MinCostFlowProblem<i64>, ParameterError> {
+    let p = parameters;
+    check(seed > 0 && seed < 2147483647, "seed must be in [1, 2^31 - 2]")?;
+    check(p.width >= 1 && p.height >= 1 && p.width * p.height >= 2, "the grid must have at least 2 nodes")?;
+    check(p.highway_spacing >= 1, "highway_spacing must be positive")?;
+    check(p.capacity >= 0, "capacity must be non-negative")?;
+    check(p.pairs >= 1 && 2 * p.pairs <= p.width * p.height, "pairs must be positive and at most half the number of nodes")?;
+    check(p.supply_per_pair >= 0, "supply_per_pair must be non-negative")?;
+
+    let mut random = Random::new(seed);
+    let (w, h) = (p.width, p.height);
+    let mut problem = MinCostFlowProblem::new(w * h);
+
+    let mut position = Vec::with_capacity(w * h);
+    for r in 0..h {
+        for c in 0..w {
+            let x = 100 * c as i64 + random.next(-30, 30);
+            let y = 100 * r as i64 + random.next(-30, 30);
+            position.push((x, y));
+        }
+    }
+
+    let spacing = p.highway_spacing;
+    let highway_capacity = p.capacity * spacing as i64;
+    let add_road = |problem: &mut MinCostFlowProblem<i64>, random: &mut Random, u: usize, v: usize, speed: i64, capacity: i64| {
+        let (dx, dy) = (position[u].0 - position[v].0, position[u].1 - position[v].1);
+        let length = ((dx * dx + dy * dy) as f64).sqrt();
+        for &(from, to) in [(u, v), (v, u)].iter() {
+            let congestion = random.next(100, 150) as f64 / 100.0;
+            let cost = (length * congestion / speed as f64).round() as i64;
+            problem.add_directed_edge(from, to, 0, capacity, cost.max(1));
+        }
+    };
+
+    for r in 0..h {
+        for c in 0..w {
+            let u = r * w + c;
+            if c + 1 < w {
+                let (speed, capacity) = if r % spacing == 0 { (2, highway_capacity) } else { (1, p.capacity) };
+                add_road(&mut problem, &mut random, u, u + 1, speed, capacity);
+            }
+            if r + 1 < h {
+                let (speed, capacity) = if c % spacing == 0 { (2, highway_capacity) } else { (1, p.capacity) };
+                add_road(&mut problem, &mut random, u, u + w, speed, capacity);
+            }
+            if spacing > 1 && r % spacing == 0 && c % spacing == 0 {
+                if c + spacing < w {
+                    add_road(&mut problem, &mut random, u, u + spacing, 3, highway_capacity);
+                }
+                if r + spacing < h {
+                    add_road(&mut problem, &mut random, u, u + spacing * w, 3, highway_capacity);
+                }
+            }
+        }
+    }
+
+    let terminals = random.sample(w * h, 2 * p.pairs);
+    for k in 0..p.pairs {
+        problem.add_supply(terminals[2 * k], p.supply_per_pair);
+        problem.add_supply(terminals[2 * k + 1], -p.supply_per_pair);
+    }
+    Ok(problem)
+}
@@ -0,0 +1,109 @@
+use generator::goto::{goto, GotoParameters};
+use generator::gridgen::{gridgen, GridgenParameters};
+use generator::road::{road, RoadParameters};
+use generator::ParameterError;
+use minimum_cost_flow_common::{MinCostFlowProblem, Status};
+use network_simplex::NetworkSimplex;
+
+const GRIDGEN: GridgenParameters = GridgenParameters { width: 6, height: 4, arcs: 120, sources: 3, sinks: 2, supply: 100, min_cost: -5, max_cost: 50, min_capacity: 0, max_capacity: 30 };
+const GOTO: GotoParameters = GotoParameters { width: 8, height: 5, degree: 6, max_capacity: 100, max_cost: 1000 };
+const ROAD: RoadParameters = RoadParameters { width: 7, height: 6, highway_spacing: 3, capacity: 1000, pairs: 5, supply_per_pair: 20 };
+
+fn message<T>(result: Result<T, ParameterError>) -> String {
+    match result {
+        Err(e) => e.message,
+        Ok(_) => panic!("generated"),
+    }
+}
+
+fn assert_optimal(problem: &MinCostFlowProblem<i64>) {
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn is_deterministic() {
+    assert_eq!(gridgen(7, &GRIDGEN), gridgen(7, &GRIDGEN));
+    assert_ne!(gridgen(7, &GRIDGEN), gridgen(8, &GRIDGEN));
+    assert_eq!(goto(7, &GOTO), goto(7, &GOTO));
+    assert_ne!(goto(7, &GOTO), goto(8, &GOTO));
+    assert_eq!(road(7, &ROAD), road(7, &ROAD));
+    assert_ne!(road(7, &ROAD), road(8, &ROAD));
+}
+
+#[test]
+fn generates_the_requested_sizes() {
+    let problem = gridgen(1, &GRIDGEN).unwrap();
+    assert_eq!((problem.num_of_nodes, problem.num_of_edges()), (24, 120));
+    assert_eq!(problem.supply.iter().filter(|&&b| b > 0).sum::<i64>(), 100);
+
+    let problem = goto(1, &GOTO).unwrap();
+    assert_eq!((problem.num_of_nodes, problem.num_of_edges()), (40, 240));
+
+    // 71 local or highway roads and 7 express roads, each in both directions
+    let problem = road(1, &ROAD).unwrap();
+    assert_eq!((problem.num_of_nodes, problem.num_of_edges()), (42, 2 * (6 * 6 + 7 * 5 + 7)));
+    assert_eq!(problem.supply.iter().filter(|&&b| b > 0).count(), 5);
+}
+
+#[test]
+fn validates_parameters() {
+    let seed = "seed must be in [1, 2^31 - 2]";
+    let cases = [
+        (gridgen(0, &GRIDGEN), seed),
+        (gridgen(2147483647, &GRIDGEN), seed),
+        (gridgen(1, &GridgenParameters { width: 1, height: 1, arcs: 0, ..GRIDGEN }), "the grid must have at least 2 nodes"),
+        (gridgen(1, &GridgenParameters { arcs: 75, ..GRIDGEN }), "arcs must be at least the number of grid arcs"),
+        (gridgen(1, &GridgenParameters { sources: 0, ..GRIDGEN }), "sources and sinks must be positive and at most the number of nodes"),
+        (gridgen(1, &GridgenParameters { sources: 20, sinks: 5, ..GRIDGEN }), "sources and sinks must be positive and at most the number of nodes"),
+        (gridgen(1, &GridgenParameters { supply: -1, ..GRIDGEN }), "supply must be non-negative"),
+        (gridgen(1, &GridgenParameters { min_cost: 51, ..GRIDGEN }), "min_cost must be at most max_cost"),
+        (gridgen(1, &GridgenParameters { min_capacity: -1, ..GRIDGEN }), "capacities must satisfy 0 <= min_capacity <= max_capacity"),
+        (gridgen(1, &GridgenParameters { min_capacity: 31, ..GRIDGEN }), "capacities must satisfy 0 <= min_capacity <= max_capacity"),
+        (goto(-1, &GOTO), seed),
+        (goto(1, &GotoParameters { height: 1, ..GOTO }), "width and height must be at least 2"),
+        (goto(1, &GotoParameters { degree: 1, ..GOTO }), "degree must be at least 2"),
+        (goto(1, &GotoParameters { max_capacity: 0, ..GOTO }), "max_capacity must be positive"),
+        (goto(1, &GotoParameters { max_cost: -1, ..GOTO }), "max_cost must be non-negative"),
+        (road(0, &ROAD), seed),
+        (road(1, &RoadParameters { width: 1, height: 1, ..ROAD }), "the grid must have at least 2 nodes"),
+        (road(1, &RoadParameters { highway_spacing: 0, ..ROAD }), "highway_spacing must be positive"),
+        (road(1, &RoadParameters { capacity: -1, ..ROAD }), "capacity must be non-negative"),
+        (road(1, &RoadParameters { pairs: 0, ..ROAD }), "pairs must be positive and at most half the number of nodes"),
+        (road(1, &RoadParameters { pairs: 22, ..ROAD }), "pairs must be positive and at most half the number of nodes"),
+        (road(1, &RoadParameters { supply_per_pair: -1, ..ROAD }), "supply_per_pair must be non-negative"),
+    ];
+    for (result, expected) in cases.iter().cloned() {
+        assert_eq!(message(result), expected);
+    }
+}
+
+#[test]
+fn gridgen_is_feasible() {
+    // even when the extra arcs have no capacity, and on a single row
+    for seed in 1..=5 {
+        assert_optimal(&gridgen(seed, &GRIDGEN).unwrap());
+        assert_optimal(&gridgen(seed, &GridgenParameters { max_capacity: 0, ..GRIDGEN }).unwrap());
+        assert_optimal(&gridgen(seed, &GridgenParameters { width: 10, height: 1, arcs: 30, ..GRIDGEN }).unwrap());
+    }
+}
+
+#[test]
+fn goto_is_feasible() {
+    for seed in 1..=5 {
+        assert_optimal(&goto(seed, &GOTO).unwrap());
+        assert_optimal(&goto(seed, &GotoParameters { width: 2, height: 2, degree: 2, ..GOTO }).unwrap());
+        assert_optimal(&goto(seed, &GotoParameters { width: 16, height: 3, degree: 10, max_capacity: 1, max_cost: 0 }).unwrap());
+    }
+}
+
+#[test]
+fn road_is_feasible_with_enough_capacity() {
+    for seed in 1..=5 {
+        assert_optimal(&road(seed, &ROAD).unwrap());
+    }
+    // without capacity no supply can be shipped
+    let problem = road(1, &RoadParameters { capacity: 0, ..ROAD }).unwrap();
+    assert_eq!(problem.solve::<NetworkSimplex<i64>>().status, Status::Infeasible);
+}
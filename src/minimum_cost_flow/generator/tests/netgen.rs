@@ -0,0 +1,36 @@
+use generator::netgen::{netgen, read_parameters};
+
+// the p, n and a lines of a DIMACS file, with the arcs sorted. the fixture does not keep the comments
+// and the order of the arcs of the C output
+fn problem_lines(dimacs: &str) -> Vec<String> {
+    let mut lines: Vec<String> = dimacs.lines().filter(|line| !line.starts_with('c')).map(|line| line.to_string()).collect();
+    let arcs = lines.iter().position(|line| line.starts_with('a')).unwrap_or(lines.len());
+    lines[arcs..].sort_by_key(|line| line.split_whitespace().skip(1).take(2).map(|t| t.parse::<usize>().unwrap()).collect::<Vec<_>>());
+    lines
+}
+
+#[test]
+fn reproduces_netgen() {
+    // the arcs and supplies of an instance generated by the C code, as kept by the networkx tests
+    let fixture = include_str!("fixtures/netgen-2.min");
+    let input = "13502460 1 400 8 60 1416 1 10000 400000 5 50 30 40 16000 30000\n";
+    let (seed, problem, parameters) = read_parameters(input.as_bytes()).unwrap()[0];
+    let network = netgen(seed, &parameters).unwrap();
+    assert_eq!(network.arcs.len(), 1416);
+
+    let mut output = Vec::new();
+    network.write_dimacs(problem, &mut output).unwrap();
+    assert_eq!(problem_lines(&String::from_utf8(output).unwrap()), problem_lines(fixture));
+}
+
+#[test]
+fn writes_dimacs() {
+    // the whole output of the port for a minimum cost flow, an assignment and a maximum flow problem.
+    // it is not the output of netgen.c, but it keeps the header and the arc order from changing
+    let input = "1 1 10 2 2 16 1 9 50 0 0 30 50 5 20\n1 2 6 3 3 9 1 9 3 0 0 0 0 1 1\n5 3 6 1 1 10 1 1 40 0 0 0 100 5 20\n0\n";
+    let mut output = Vec::new();
+    for (seed, problem, parameters) in read_parameters(input.as_bytes()).unwrap() {
+        netgen(seed, &parameters).unwrap().write_dimacs(problem, &mut output).unwrap();
+    }
+    assert_eq!(String::from_utf8(output).unwrap(), include_str!("fixtures/netgen-port.txt"));
+}
@@ -0,0 +1,304 @@
+#![allow(clippy::needless_range_loop)]
+use minimum_cost_flow_common::{Flow, MinCostFlowProblem, ProblemEdge, Solution, Status};
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// LEMON graph format
+// @nodes
+// label supply
+// a     10
+// b     -10
+// @arcs
+//       label capacity cost lower
+// a b   e0    10       5    0
+//
+// the first two columns of @arcs are the labels of the nodes
+// missing maps are 0, except capacity which is F::max_value()
+// unknown maps and other sections (@attributes etc.) are ignored. undirected @edges sections are an error
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+fn parse_error<T>(line: usize, message: String) -> Result<T, Error> {
+    Err(Error::Parse { line, message })
+}
+
+#[derive(PartialEq)]
+enum Section {
+    Nodes,
+    Arcs,
+    Other,
+}
+
+// node labels are stored in node_names, arc labels in the names of the edges
+pub fn read_problem<F: Flow, R: BufRead>(reader: R) -> Result<MinCostFlowProblem<F>, Error> {
+    let mut section = Section::Other;
+    let mut header: Option<Vec<String>> = None;
+    let mut seen_nodes = false;
+    let mut seen_arcs = false;
+
+    let mut labels: Vec<Option<String>> = Vec::new();
+    let mut supply = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut edges = Vec::new();
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        line_number += 1;
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('@') {
+            section = match line.split_whitespace().next().unwrap() {
+                "@nodes" => {
+                    if seen_nodes {
+                        return parse_error(line_number, "duplicate @nodes section".to_string());
+                    }
+                    seen_nodes = true;
+                    Section::Nodes
+                }
+                "@arcs" => {
+                    if !seen_nodes {
+                        return parse_error(line_number, "@arcs section before @nodes section".to_string());
+                    }
+                    if seen_arcs {
+                        return parse_error(line_number, "duplicate @arcs section".to_string());
+                    }
+                    seen_arcs = true;
+                    Section::Arcs
+                }
+                "@edges" => return parse_error(line_number, "undirected @edges section is not supported".to_string()),
+                _ => Section::Other,
+            };
+            header = None;
+            continue;
+        }
+
+        if section == Section::Other {
+            continue;
+        }
+
+        let tokens = match tokenize(line) {
+            Ok(tokens) => tokens,
+            Err(message) => return parse_error(line_number, message),
+        };
+
+        let maps = match header.as_ref() {
+            Some(maps) => maps,
+            None => {
+                // as in LEMON, a lone - is the header of a section without maps
+                header = Some(if tokens == ["-"] { Vec::new() } else { tokens });
+                continue;
+            }
+        };
+        let column = |name: &str| maps.iter().position(|m| m == name);
+
+        if section == Section::Nodes {
+            if tokens.len() != maps.len() {
+                return parse_error(line_number, format!("node must have {} fields but has {}", maps.len(), tokens.len()));
+            }
+            let u = labels.len();
+            let label = column("label").map(|c| tokens[c].clone());
+            if let Some(label) = label.as_ref() {
+                if index.insert(label.clone(), u).is_some() {
+                    return parse_error(line_number, format!("duplicate node label '{}'", label));
+                }
+            }
+            labels.push(label);
+            supply.push(match column("supply") {
+                Some(c) => parse_value(&tokens[c], line_number)?,
+                None => F::zero(),
+            });
+        } else {
+            if index.is_empty() && !labels.is_empty() {
+                return parse_error(line_number, "@nodes section must have a label map".to_string());
+            }
+            if tokens.len() != maps.len() + 2 {
+                return parse_error(line_number, format!("arc must have {} fields but has {}", maps.len() + 2, tokens.len()));
+            }
+            let from = find_node(&index, &tokens[0], line_number)?;
+            let to = find_node(&index, &tokens[1], line_number)?;
+            let value = |name: &str, default: F| match column(name) {
+                Some(c) => parse_value(&tokens[c + 2], line_number),
+                None => Ok(default),
+            };
+            let lower = value("lower", F::zero())?;
+            let upper = value("capacity", F::max_value())?;
+            let cost = value("cost", F::zero())?;
+            if lower > upper {
+                return parse_error(line_number, format!("lower bound {} is greater than capacity {}", lower, upper));
+            }
+            let name = column("label").map(|c| tokens[c + 2].clone());
+            edges.push(ProblemEdge { from, to, lower, upper, cost, name });
+        }
+    }
+
+    if !seen_nodes {
+        return parse_error(line_number, "missing @nodes section".to_string());
+    }
+
+    let mut problem = MinCostFlowProblem::new(labels.len());
+    problem.supply = supply;
+    problem.node_names = labels;
+    problem.edges = edges;
+    Ok(problem)
+}
+
+pub fn write_problem<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, writer: W) -> io::Result<()> {
+    write(problem, None, writer)
+}
+
+// the network with a flow map on the arcs and a potential map on the nodes
+pub fn write_solution<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, solution: &Solution<F>, writer: W) -> io::Result<()> {
+    assert_eq!(solution.status, Status::Optimal);
+    write(problem, Some(solution), writer)
+}
+
+fn write<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, solution: Option<&Solution<F>>, mut writer: W) -> io::Result<()> {
+    let with_potentials = solution.is_some_and(|s| !s.potentials.is_empty());
+    let labels = unique_labels(problem.node_names.iter().map(|name| name.as_ref()));
+    let edge_labels = unique_labels(problem.edges.iter().map(|e| e.name.as_ref()));
+
+    writeln!(writer, "@nodes")?;
+    writeln!(writer, "label\tsupply{}", if with_potentials { "\tpotential" } else { "" })?;
+    for u in 0..problem.num_of_nodes {
+        write!(writer, "{}\t{}", labels[u], problem.supply[u])?;
+        if with_potentials {
+            write!(writer, "\t{}", solution.unwrap().potentials[u])?;
+        }
+        writeln!(writer)?;
+    }
+
+    writeln!(writer, "@arcs")?;
+    writeln!(writer, "\t\tlabel\tcapacity\tcost\tlower{}", if solution.is_some() { "\tflow" } else { "" })?;
+    for (i, e) in problem.edges.iter().enumerate() {
+        write!(writer, "{}\t{}\t{}\t{}\t{}\t{}", labels[e.from], labels[e.to], edge_labels[i], e.upper, e.cost, e.lower)?;
+        if let Some(solution) = solution {
+            write!(writer, "\t{}", solution.flows[i])?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+// quoted labels that are all different. the names are kept, except a repeated name, and the others are
+// labeled by their index, with a suffix _1, _2, ... if it is the name of another one
+fn unique_labels<'a>(names: impl Iterator<Item = Option<&'a String>>) -> Vec<String> {
+    let names: Vec<Option<&String>> = names.collect();
+    let mut used = HashSet::new();
+    let mut labels: Vec<Option<String>> = names.iter().map(|name| name.filter(|name| used.insert(name.to_string())).cloned()).collect();
+    for (i, label) in labels.iter_mut().enumerate() {
+        if label.is_none() {
+            let mut candidate = i.to_string();
+            let mut k = 0;
+            while !used.insert(candidate.clone()) {
+                k += 1;
+                candidate = format!("{}_{}", i, k);
+            }
+            *label = Some(candidate);
+        }
+    }
+    labels.into_iter().map(|label| quote(&label.unwrap())).collect()
+}
+
+fn find_node(index: &HashMap<String, usize>, label: &str, line_number: usize) -> Result<usize, Error> {
+    match index.get(label) {
+        Some(&u) => Ok(u),
+        None => parse_error(line_number, format!("unknown node label '{}'", label)),
+    }
+}
+
+fn parse_value<F: Flow>(token: &str, line_number: usize) -> Result<F, Error> {
+    match token.parse::<i128>().ok().and_then(F::from_i128) {
+        Some(x) => Ok(x),
+        None => parse_error(line_number, format!("invalid value '{}'", token)),
+    }
+}
+
+// tokens are separated by whitespace. "..." may contain whitespace and \" \\ \n \t
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+
+        let mut token = String::new();
+        match chars.peek() {
+            None => break,
+            Some('"') => {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => token.push('\n'),
+                            Some('t') => token.push('\t'),
+                            Some(c) => token.push(c),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some(c) => token.push(c),
+                    }
+                }
+            }
+            Some(_) => {
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+            }
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn quote(token: &str) -> String {
+    if !token.is_empty() && !token.starts_with('#') && !token.starts_with('@') && !token.chars().any(|c| c.is_whitespace() || c == '"' || c == '\\') {
+        return token.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    for c in token.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,133 @@
+use lgf::{read_problem, write_problem, write_solution, Error};
+use minimum_cost_flow_common::{MinCostFlowProblem, Solution, Status};
+
+fn read(text: &str) -> Result<MinCostFlowProblem<i64>, Error> {
+    read_problem(text.as_bytes())
+}
+
+fn write(problem: &MinCostFlowProblem<i64>) -> String {
+    let mut written = Vec::new();
+    write_problem(problem, &mut written).unwrap();
+    String::from_utf8(written).unwrap()
+}
+
+fn parse_error<T>(result: Result<T, Error>) -> (usize, String) {
+    match result {
+        Err(Error::Parse { line, message }) => (line, message),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("parsed"),
+    }
+}
+
+#[test]
+fn reads_problem() {
+    let text = "# a comment\n@nodes\nlabel supply x\na 10 1\n\"b c\" -10 2\n@arcs\n label cost capacity\na \"b c\" e0 5 10\n\"b c\" a e1 -1 3\n@attributes\nsource a\n";
+    let problem = read(text).unwrap();
+    assert_eq!(problem.node_names, vec![Some("a".to_string()), Some("b c".to_string())]);
+    assert_eq!(problem.supply, vec![10, -10]);
+    assert_eq!(problem.edges.len(), 2);
+    let e = &problem.edges[1];
+    assert_eq!((e.from, e.to, e.lower, e.upper, e.cost, e.name.as_deref()), (1, 0, 0, 3, -1, Some("e1")));
+
+    // missing maps are 0, and the capacity is unbounded
+    let problem = read("@nodes\nlabel\n0\n1\n@arcs\nunknown\n0 1 9\n").unwrap();
+    assert_eq!(problem.supply, vec![0, 0]);
+    let e = &problem.edges[0];
+    assert_eq!((e.lower, e.upper, e.cost, e.name.as_ref()), (0, i64::MAX, 0, None));
+
+    // - is an empty map list, not a map named -
+    let problem = read("@nodes\nlabel\n0\n1\n@arcs\n-\n0 1\n1 1\n").unwrap();
+    assert_eq!(problem.edges.len(), 2);
+    let e = &problem.edges[1];
+    assert_eq!((e.from, e.to, e.lower, e.upper, e.cost, e.name.as_ref()), (1, 1, 0, i64::MAX, 0, None));
+    assert_eq!(parse_error(read("@nodes\nlabel\n0\n@arcs\n-\n0 0 1\n")), (6, "arc must have 2 fields but has 3".to_string()));
+}
+
+#[test]
+fn round_trips() {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.set_node_name(0, "s");
+    problem.set_node_name(1, "a \"quoted\"\tname");
+    problem.set_node_name(2, "#t");
+    let e = problem.add_directed_edge(0, 1, -2, 5, 3);
+    problem.set_edge_name(e, "first");
+    problem.add_directed_edge(1, 2, 0, 4, -1);
+    problem.add_directed_edge(2, 2, 1, 1, 7);
+    problem.add_supply(0, 4);
+    problem.add_supply(2, -4);
+
+    let text = write(&problem);
+    let read_back = read(&text).unwrap();
+    assert_eq!(read_back.node_names, problem.node_names);
+    assert_eq!(read_back.supply, problem.supply);
+    for (e, f) in problem.edges.iter().zip(read_back.edges.iter()) {
+        assert_eq!((e.from, e.to, e.lower, e.upper, e.cost), (f.from, f.to, f.lower, f.upper, f.cost));
+    }
+    // edges without a name are labeled by their index
+    let names: Vec<Option<&str>> = read_back.edges.iter().map(|e| e.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("first"), Some("1"), Some("2")]);
+    assert_eq!(write(&read_back), text);
+}
+
+#[test]
+fn writes_unique_labels() {
+    // node 0 is named after the index of node 1, and nodes 2 and 3 have the same name
+    let mut problem = MinCostFlowProblem::new(4);
+    problem.set_node_name(0, "1");
+    problem.set_node_name(2, "x");
+    problem.set_node_name(3, "x");
+    for u in 0..4 {
+        let e = problem.add_directed_edge(u, (u + 1) % 4, 0, 1, u as i64);
+        problem.set_edge_name(e, "e");
+    }
+
+    let read_back = read(&write(&problem)).unwrap();
+    let labels: Vec<&str> = read_back.node_names.iter().map(|name| name.as_deref().unwrap()).collect();
+    assert_eq!(labels, vec!["1", "1_1", "x", "3"]);
+    let names: Vec<&str> = read_back.edges.iter().map(|e| e.name.as_deref().unwrap()).collect();
+    assert_eq!(names, vec!["e", "1", "2", "3"]);
+    for (e, f) in problem.edges.iter().zip(read_back.edges.iter()) {
+        assert_eq!((e.from, e.to, e.cost), (f.from, f.to, f.cost));
+    }
+}
+
+#[test]
+fn writes_solution() {
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, 5, 2);
+    problem.add_supply(0, 3);
+    problem.add_supply(1, -3);
+    let solution = Solution { status: Status::Optimal, flows: vec![3], potentials: vec![0, 2], cost: Some(6) };
+
+    let mut written = Vec::new();
+    write_solution(&problem, &solution, &mut written).unwrap();
+    let text = String::from_utf8(written).unwrap();
+    assert_eq!(text, "@nodes\nlabel\tsupply\tpotential\n0\t3\t0\n1\t-3\t2\n@arcs\n\t\tlabel\tcapacity\tcost\tlower\tflow\n0\t1\t0\t5\t2\t0\t3\n");
+
+    // the flow and potential maps are ignored when reading
+    let read_back = read(&text).unwrap();
+    assert_eq!(read_back.supply, problem.supply);
+    assert_eq!((read_back.edges[0].upper, read_back.edges[0].cost), (5, 2));
+}
+
+#[test]
+fn rejects_bad_input() {
+    let cases = [
+        ("", 0, "missing @nodes section"),
+        ("@arcs\n", 1, "@arcs section before @nodes section"),
+        ("@nodes\nlabel\n@nodes\n", 3, "duplicate @nodes section"),
+        ("@nodes\nlabel\n@arcs\n-\n@arcs\n", 5, "duplicate @arcs section"),
+        ("@nodes\nlabel\na\n@edges\nlabel\na a 0\n", 4, "undirected @edges section is not supported"),
+        ("@nodes\nlabel supply\na\n", 3, "node must have 2 fields but has 1"),
+        ("@nodes\nlabel\na\na\n", 4, "duplicate node label 'a'"),
+        ("@nodes\nlabel supply\na x\n", 3, "invalid value 'x'"),
+        ("@nodes\nlabel\n\"a\n", 3, "unterminated string"),
+        ("@nodes\nsupply\n1\n@arcs\n-\n0 0\n", 6, "@nodes section must have a label map"),
+        ("@nodes\nlabel\na\n@arcs\ncost\na a\n", 6, "arc must have 3 fields but has 2"),
+        ("@nodes\nlabel\na\n@arcs\nx\na b 0\n", 6, "unknown node label 'b'"),
+        ("@nodes\nlabel\na\n@arcs\nlower capacity\na a 2 1\n", 6, "lower bound 2 is greater than capacity 1"),
+    ];
+    for &(text, line, message) in cases.iter() {
+        assert_eq!(parse_error(read(text)), (line, message.to_string()), "{:?}", text);
+    }
+}
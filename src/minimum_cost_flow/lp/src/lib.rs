@@ -0,0 +1,228 @@
+use minimum_cost_flow_common::{Flow, MinCostFlowProblem, Solution, Status};
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// the linear program of a minimum cost flow problem
+// minimize   sum cost[i] * x<i>
+// subject to n<u>: sum_{i out of u} x<i> - sum_{i into u} x<i> = supply[u]
+//            lower[i] <= x<i> <= upper[i]
+// upper = F::max_value() is written as no upper bound
+
+// terms per line in the LP format
+const TERMS_PER_LINE: usize = 10;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+fn parse_error<T>(line: usize, message: String) -> Result<T, Error> {
+    Err(Error::Parse { line, message })
+}
+
+// (edge, coefficient) of the conservation row of each node. self loops cancel out
+fn rows<F: Flow>(problem: &MinCostFlowProblem<F>) -> Vec<Vec<(usize, i32)>> {
+    let mut rows = vec![vec![]; problem.num_of_nodes];
+    for (i, e) in problem.edges.iter().enumerate() {
+        if e.from != e.to {
+            rows[e.from].push((i, 1));
+            rows[e.to].push((i, -1));
+        }
+    }
+    rows
+}
+
+// CPLEX LP format
+pub fn write_lp<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "\\ minimum cost flow: x<i> is the flow of edge i, n<u> is the flow conservation of node u")?;
+
+    writeln!(writer, "Minimize")?;
+    let mut objective: Vec<(String, usize)> = problem.edges.iter().enumerate().filter(|(_, e)| e.cost != F::zero()).map(|(i, e)| (e.cost.to_string(), i)).collect();
+    if objective.is_empty() && problem.num_of_edges() > 0 {
+        objective.push(("0".to_string(), 0));
+    }
+    write!(writer, " obj:")?;
+    write_terms(&mut writer, &objective)?;
+    writeln!(writer)?;
+
+    writeln!(writer, "Subject To")?;
+    for (u, row) in rows(problem).iter().enumerate() {
+        let mut terms: Vec<(String, usize)> = row.iter().map(|&(i, c)| (c.to_string(), i)).collect();
+        write!(writer, " n{}:", u)?;
+        if problem.num_of_edges() == 0 {
+            // there is no variable to write a zero term with, and the row is infeasible unless the supply is 0
+            write!(writer, " 0")?;
+        } else {
+            if terms.is_empty() {
+                terms.push(("0".to_string(), 0));
+            }
+            write_terms(&mut writer, &terms)?;
+        }
+        writeln!(writer, " = {}", problem.supply[u])?;
+    }
+
+    writeln!(writer, "Bounds")?;
+    for (i, e) in problem.edges.iter().enumerate() {
+        if e.lower == e.upper {
+            writeln!(writer, " x{} = {}", i, e.lower)?;
+        } else if e.upper == F::max_value() {
+            writeln!(writer, " x{} >= {}", i, e.lower)?;
+        } else {
+            writeln!(writer, " {} <= x{} <= {}", e.lower, i, e.upper)?;
+        }
+    }
+
+    writeln!(writer, "End")
+}
+
+fn write_terms<W: Write>(writer: &mut W, terms: &[(String, usize)]) -> io::Result<()> {
+    for (k, (coefficient, i)) in terms.iter().enumerate() {
+        if k > 0 && k % TERMS_PER_LINE == 0 {
+            write!(writer, "\n   ")?;
+        }
+        match coefficient.strip_prefix('-') {
+            Some(c) => write!(writer, " - {} x{}", c, i)?,
+            None => write!(writer, " + {} x{}", coefficient, i)?,
+        }
+    }
+    Ok(())
+}
+
+// free MPS format
+pub fn write_mps<F: Flow, W: Write>(problem: &MinCostFlowProblem<F>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "NAME mincostflow")?;
+
+    writeln!(writer, "ROWS")?;
+    writeln!(writer, " N obj")?;
+    for u in 0..problem.num_of_nodes {
+        writeln!(writer, " E n{}", u)?;
+    }
+
+    // every column has an objective entry, so that columns of self loops are not lost
+    let mut columns = vec![vec![]; problem.num_of_edges()];
+    for (u, row) in rows(problem).iter().enumerate() {
+        for &(i, c) in row.iter() {
+            columns[i].push((u, c));
+        }
+    }
+    writeln!(writer, "COLUMNS")?;
+    for (i, e) in problem.edges.iter().enumerate() {
+        writeln!(writer, " x{} obj {}", i, e.cost)?;
+        for &(u, c) in columns[i].iter() {
+            writeln!(writer, " x{} n{} {}", i, u, c)?;
+        }
+    }
+
+    writeln!(writer, "RHS")?;
+    for u in 0..problem.num_of_nodes {
+        if problem.supply[u] != F::zero() {
+            writeln!(writer, " rhs n{} {}", u, problem.supply[u])?;
+        }
+    }
+
+    writeln!(writer, "BOUNDS")?;
+    for (i, e) in problem.edges.iter().enumerate() {
+        if e.lower == e.upper {
+            writeln!(writer, " FX bnd x{} {}", i, e.lower)?;
+            continue;
+        }
+        writeln!(writer, " LO bnd x{} {}", i, e.lower)?;
+        if e.upper != F::max_value() {
+            writeln!(writer, " UP bnd x{} {}", i, e.upper)?;
+        }
+    }
+
+    writeln!(writer, "ENDATA")
+}
+
+// reads `<name> <value>` lines. lines starting with '#' are comments
+// x<i> is the primal value of edge i, n<u> is the dual value of the row of node u. a Gurobi .sol file has only
+// the primal values, so the duals have to be written by the caller in the same format
+// the dual of a row is the negated potential (cost - dual[from] + dual[to] >= 0 on the residual network)
+// other names are ignored. the values must be integral
+// use MinCostFlowProblem::check_solution to validate the result
+pub fn read_solution<F: Flow, R: BufRead>(problem: &MinCostFlowProblem<F>, reader: R) -> Result<Solution<F>, Error> {
+    let mut flows = vec![None; problem.num_of_edges()];
+    let mut potentials = vec![None; problem.num_of_nodes];
+    let mut line_number = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        line_number += 1;
+
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.is_empty() || v[0].starts_with('#') {
+            continue;
+        }
+        if v.len() != 2 {
+            return parse_error(line_number, format!("line must have 2 fields but has {}", v.len()));
+        }
+
+        let (values, index) = match (v[0].strip_prefix('x'), v[0].strip_prefix('n')) {
+            (Some(i), _) => (&mut flows, i),
+            (_, Some(u)) => (&mut potentials, u),
+            _ => continue,
+        };
+        let index: usize = match index.parse() {
+            Ok(index) if index < values.len() => index,
+            _ => return parse_error(line_number, format!("unknown variable or row '{}'", v[0])),
+        };
+        if values[index].is_some() {
+            return parse_error(line_number, format!("duplicate value of '{}'", v[0]));
+        }
+        let value: F = parse_integral(v[1], line_number)?;
+        values[index] = Some(if v[0].starts_with('n') { -value } else { value });
+    }
+
+    let flows: Vec<F> = flows.into_iter().map(|flow| flow.unwrap_or_else(F::zero)).collect();
+    let potentials: Vec<F> = if potentials.iter().all(|p| p.is_none()) {
+        Vec::new()
+    } else {
+        match potentials.iter().position(|p| p.is_none()) {
+            Some(u) => return parse_error(line_number, format!("missing dual value of 'n{}'", u)),
+            None => potentials.into_iter().map(|p| p.unwrap()).collect(),
+        }
+    };
+    let cost = Some(problem.cost(&flows));
+
+    Ok(Solution { status: Status::Optimal, flows, potentials, cost })
+}
+
+// LP solvers write floating point numbers
+fn parse_integral<F: Flow>(token: &str, line_number: usize) -> Result<F, Error> {
+    if let Some(x) = token.parse::<i128>().ok().and_then(F::from_i128) {
+        return Ok(x);
+    }
+
+    let x: f64 = match token.parse() {
+        Ok(x) => x,
+        Err(_) => return parse_error(line_number, format!("invalid value '{}'", token)),
+    };
+    let rounded = x.round();
+    if (x - rounded).abs() > 1e-6 * f64::max(1.0, x.abs()) {
+        return parse_error(line_number, format!("value '{}' is not integral", token));
+    }
+    match F::from_f64(rounded) {
+        Some(x) => Ok(x),
+        None => parse_error(line_number, format!("value '{}' is out of range", token)),
+    }
+}
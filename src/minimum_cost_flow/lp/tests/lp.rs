@@ -0,0 +1,180 @@
+use lp::{read_solution, write_lp, write_mps, Error};
+use minimum_cost_flow_common::{MinCostFlowProblem, Solution, Status};
+
+// the line and the message of a parse error
+fn parse_error<T>(result: Result<T, Error>) -> (usize, String) {
+    match result {
+        Err(Error::Parse { line, message }) => (line, message),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("parsed"),
+    }
+}
+
+// a free upper bound with a negative lower bound, a fixed edge and a self loop
+fn bounds() -> MinCostFlowProblem<i64> {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, -2, i64::MAX, 3);
+    problem.add_directed_edge(1, 2, 1, 1, -1);
+    problem.add_directed_edge(2, 2, 0, 4, 2);
+    problem.add_directed_edge(0, 2, 0, 5, 0);
+    problem.add_supply(0, 3);
+    problem.add_supply(2, -3);
+    problem
+}
+
+fn lp(problem: &MinCostFlowProblem<i64>) -> String {
+    let mut written = Vec::new();
+    write_lp(problem, &mut written).unwrap();
+    String::from_utf8(written).unwrap()
+}
+
+fn mps(problem: &MinCostFlowProblem<i64>) -> String {
+    let mut written = Vec::new();
+    write_mps(problem, &mut written).unwrap();
+    String::from_utf8(written).unwrap()
+}
+
+#[test]
+fn writes_lp() {
+    let expected = "\\ minimum cost flow: x<i> is the flow of edge i, n<u> is the flow conservation of node u
+Minimize
+ obj: + 3 x0 - 1 x1 + 2 x2
+Subject To
+ n0: + 1 x0 + 1 x3 = 3
+ n1: - 1 x0 + 1 x1 = 0
+ n2: - 1 x1 - 1 x3 = -3
+Bounds
+ x0 >= -2
+ x1 = 1
+ 0 <= x2 <= 4
+ 0 <= x3 <= 5
+End
+";
+    assert_eq!(lp(&bounds()), expected);
+
+    // a node without edges and an objective without costs get a zero term, and long rows are wrapped
+    let mut problem = MinCostFlowProblem::new(3);
+    for _ in 0..11 {
+        problem.add_directed_edge(0, 1, 0, 1, 0);
+    }
+    let expected = "\\ minimum cost flow: x<i> is the flow of edge i, n<u> is the flow conservation of node u
+Minimize
+ obj: + 0 x0
+Subject To
+ n0: + 1 x0 + 1 x1 + 1 x2 + 1 x3 + 1 x4 + 1 x5 + 1 x6 + 1 x7 + 1 x8 + 1 x9
+    + 1 x10 = 0
+ n1: - 1 x0 - 1 x1 - 1 x2 - 1 x3 - 1 x4 - 1 x5 - 1 x6 - 1 x7 - 1 x8 - 1 x9
+    - 1 x10 = 0
+ n2: + 0 x0 = 0
+Bounds
+";
+    assert!(lp(&problem).starts_with(expected), "{}", lp(&problem));
+}
+
+#[test]
+fn writes_rows_without_edges() {
+    // the supply can not be shipped, so the program must be infeasible
+    let mut problem: MinCostFlowProblem<i64> = MinCostFlowProblem::new(2);
+    problem.add_supply(0, 1);
+    problem.add_supply(1, -1);
+    let expected = "\\ minimum cost flow: x<i> is the flow of edge i, n<u> is the flow conservation of node u
+Minimize
+ obj:
+Subject To
+ n0: 0 = 1
+ n1: 0 = -1
+Bounds
+End
+";
+    assert_eq!(lp(&problem), expected);
+    assert_eq!(mps(&problem), "NAME mincostflow\nROWS\n N obj\n E n0\n E n1\nCOLUMNS\nRHS\n rhs n0 1\n rhs n1 -1\nBOUNDS\nENDATA\n");
+}
+
+#[test]
+fn writes_mps() {
+    let expected = "NAME mincostflow
+ROWS
+ N obj
+ E n0
+ E n1
+ E n2
+COLUMNS
+ x0 obj 3
+ x0 n0 1
+ x0 n1 -1
+ x1 obj -1
+ x1 n1 1
+ x1 n2 -1
+ x2 obj 2
+ x3 obj 0
+ x3 n0 1
+ x3 n2 -1
+RHS
+ rhs n0 3
+ rhs n2 -3
+BOUNDS
+ LO bnd x0 -2
+ FX bnd x1 1
+ LO bnd x2 0
+ UP bnd x2 4
+ LO bnd x3 0
+ UP bnd x3 5
+ENDATA
+";
+    assert_eq!(mps(&bounds()), expected);
+}
+
+// 4 units from 0 to 2 along 0 -> 1 -> 2, with potentials [0, 1, 2]
+fn path() -> MinCostFlowProblem<i64> {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, 5, 1);
+    problem.add_directed_edge(1, 2, 0, 5, 1);
+    problem.add_directed_edge(0, 2, 0, 2, 3);
+    problem.add_supply(0, 4);
+    problem.add_supply(2, -4);
+    problem
+}
+
+fn read(problem: &MinCostFlowProblem<i64>, text: &str) -> Result<Solution<i64>, Error> {
+    read_solution(problem, text.as_bytes())
+}
+
+#[test]
+fn reads_solution() {
+    let problem = path();
+    // LP solvers write floating point numbers, and the duals are the negated potentials
+    let text = "# Objective value = 8\nx0 4.0000000001\nx1 3.9999999999e+00\nx2 0\nn0 -0.0\nn1 -1.0\nn2 -2\nobj 8\n";
+    let solution = read(&problem, text).unwrap();
+    assert_eq!(solution, Solution { status: Status::Optimal, flows: vec![4, 4, 0], potentials: vec![0, 1, 2], cost: Some(8) });
+    problem.check_solution(&solution).unwrap();
+
+    // the duals themselves are not potentials: 0 -> 1 would have reduced cost 2 and carry flow
+    let mut duals = solution.clone();
+    duals.potentials = vec![0, -1, -2];
+    assert!(problem.check_solution(&duals).is_err());
+
+    // without duals there are no potentials, and missing primal values are 0
+    let solution = read(&problem, "x0 4\nx1 4\n").unwrap();
+    assert_eq!(solution, Solution { status: Status::Optimal, flows: vec![4, 4, 0], potentials: vec![], cost: Some(8) });
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn rejects_bad_solutions() {
+    let problem = path();
+    let cases = [
+        ("x0 4 5\n", 1, "line must have 2 fields but has 3"),
+        ("x0 3.5\n", 1, "value '3.5' is not integral"),
+        ("x0 4.001\n", 1, "value '4.001' is not integral"),
+        ("x0 four\n", 1, "invalid value 'four'"),
+        ("x0 1e30\n", 1, "value '1e30' is out of range"),
+        ("x3 0\n", 1, "unknown variable or row 'x3'"),
+        ("nx 0\n", 1, "unknown variable or row 'nx'"),
+        ("x0 4\n# again\nx0 4\n", 3, "duplicate value of 'x0'"),
+        ("n1 -1\nn1 -1\n", 2, "duplicate value of 'n1'"),
+        ("x0 4\nn0 0\nn1 -1\n", 3, "missing dual value of 'n2'"),
+    ];
+    for &(text, line, message) in cases.iter() {
+        assert_eq!(parse_error(read(&problem, text)), (line, message.to_string()), "{:?}", text);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{parse_solver, solve_cost_scaling, value, CostScalingOptions};
+use generator::{goto, gridgen, netgen, road};
+use min_cost_flow::{Algorithm, MinCostFlow, MinCostFlowProblem, Solution, Status};
+use std::fs::File;
+use std::io::BufReader;
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str = "usage: mcf bench [options] [input...]
+
+solves every instance with every configuration and writes a comparison table.
+the inputs are DIMACS files. the solutions are checked, and the exit status is 4 if a solution is wrong
+or the configurations disagree on the status or the cost
+
+options:
+  --suite <none|small|large>      generated instances. default: small if there is no input, otherwise none
+  --solvers <list>                comma separated solvers (see mcf --help)
+                                  default: cost-scaling,network-simplex,successive-shortest-path,capacity-scaling,relaxation
+  --alpha <list>                  comma separated alpha values of cost scaling. default: 5
+  --heuristics <all|default>      all 8 combinations of look-ahead, price update and price refinement,
+                                  or only the default settings of cost scaling. default: all
+  --repeat <n>                    report the fastest of n runs. default: 1";
+
+const DEFAULT_SOLVERS: &str = "cost-scaling,network-simplex,successive-shortest-path,capacity-scaling,relaxation";
+
+struct Configuration {
+    name: String,
+    algorithm: Algorithm,
+    cost_scaling: CostScalingOptions,
+}
+
+struct Instance {
+    name: String,
+    problem: MinCostFlowProblem<i64>,
+}
+
+pub fn bench(args: &[String]) -> Result<(), String> {
+    let mut inputs = Vec::new();
+    let mut suite = None;
+    let mut solvers = DEFAULT_SOLVERS.to_string();
+    let mut alphas = vec![5];
+    let mut all_heuristics = true;
+    let mut repeat = 1;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--suite" => suite = Some(value(&mut iter, arg)?.to_string()),
+            "--solvers" => solvers = value(&mut iter, arg)?.to_string(),
+            "--alpha" => {
+                let list = value(&mut iter, arg)?;
+                alphas = list.split(',').map(|a| a.parse().ok().filter(|&a| a >= 2).ok_or_else(|| format!("alpha must be an integer >= 2 but is '{}'", a))).collect::<Result<_, _>>()?;
+            }
+            "--heuristics" => {
+                all_heuristics = match value(&mut iter, arg)? {
+                    "all" => true,
+                    "default" => false,
+                    h => return Err(format!("expected all or default but got '{}'", h)),
+                }
+            }
+            "--repeat" => {
+                let n = value(&mut iter, arg)?;
+                repeat = n.parse().ok().filter(|&n| n >= 1).ok_or_else(|| format!("repeat must be a positive integer but is '{}'", n))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => inputs.push(arg.clone()),
+        }
+    }
+
+    let mut instances = match suite.as_deref().unwrap_or(if inputs.is_empty() { "small" } else { "none" }) {
+        "none" => Vec::new(),
+        "small" => generate(false)?,
+        "large" => generate(true)?,
+        s => return Err(format!("unknown suite '{}'", s)),
+    };
+    for path in inputs.iter() {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        let problem = dimacs::read_problem(BufReader::new(file)).map_err(|e| format!("{}: {}", path, e))?;
+        instances.push(Instance { name: path.clone(), problem });
+    }
+
+    let mut configurations = Vec::new();
+    for solver in solvers.split(',') {
+        let algorithm = parse_solver(solver)?.ok_or_else(|| "auto is not a solver to benchmark".to_string())?;
+        if algorithm != Algorithm::CostScalingPushRelabel {
+            configurations.push(Configuration { name: solver.to_string(), algorithm, cost_scaling: CostScalingOptions::default() });
+            continue;
+        }
+        for &alpha in alphas.iter() {
+            for bits in 0..8 {
+                let options = CostScalingOptions { alpha: Some(alpha), look_ahead: bits & 1 == 0, price_update: bits & 2 != 0, price_refinement: bits & 4 != 0, ..CostScalingOptions::default() };
+                if !all_heuristics && bits != 0 {
+                    continue;
+                }
+                let mut name = format!("cost-scaling alpha={}", alpha);
+                for &(on, heuristic) in [(options.look_ahead, "la"), (options.price_update, "pu"), (options.price_refinement, "pr")].iter() {
+                    name += if on { " +" } else { " -" };
+                    name += heuristic;
+                }
+                configurations.push(Configuration { name, algorithm, cost_scaling: options });
+            }
+        }
+    }
+
+    run(&instances, &configurations, repeat)
+}
+
+// the instances of the suites. netgen parameters are in the order of the netgen input
+fn generate(large: bool) -> Result<Vec<Instance>, String> {
+    let netgen_parameters: &[(i64, [i64; 13])] = if large { &[(13502460, [5000, 50, 50, 25000, 1, 100, 1000, 4, 4, 30, 50, 100, 500]), (4072700, [8000, 100, 100, 64000, 1, 10000, 100000, 0, 0, 0, 100, 1, 1000])] } else { &[(13502460, [200, 10, 20, 1300, 1, 100, 100000, 0, 0, 0, 100, 1, 1000]), (7, [1000, 20, 20, 3000, -50, 50, 5000, 5, 5, 20, 40, 50, 200])] };
+    let (grid, torus, roads) = if large { (100, 64, 100) } else { (30, 16, 30) };
+
+    let mut instances = Vec::new();
+    for &(seed, parms) in netgen_parameters.iter() {
+        let network = netgen::netgen(seed, &netgen::NetgenParameters::from_slice(&parms)).map_err(|e| e.to_string())?;
+        instances.push(Instance { name: format!("netgen-{}-{}", parms[0], parms[3]), problem: network.to_problem() });
+    }
+
+    let parameters = gridgen::GridgenParameters { width: grid, height: grid, arcs: 8 * grid * grid, sources: grid / 4, sinks: grid / 4, supply: 100 * grid as i64, min_cost: 1, max_cost: 1000, min_capacity: 1, max_capacity: 100 };
+    let problem = gridgen::gridgen(1, &parameters).map_err(|e| e.to_string())?;
+    instances.push(Instance { name: format!("gridgen-{}x{}", grid, grid), problem });
+
+    let parameters = goto::GotoParameters { width: torus, height: torus, degree: 8, max_capacity: 1000, max_cost: 10000 };
+    let problem = goto::goto(1, &parameters).map_err(|e| e.to_string())?;
+    instances.push(Instance { name: format!("goto-{}x{}", torus, torus), problem });
+
+    let parameters = road::RoadParameters { width: roads, height: roads, highway_spacing: 5, capacity: 100, pairs: roads, supply_per_pair: 50 };
+    let problem = road::road(1, &parameters).map_err(|e| e.to_string())?;
+    instances.push(Instance { name: format!("road-{}x{}", roads, roads), problem });
+
+    Ok(instances)
+}
+
+fn solve(problem: &MinCostFlowProblem<i64>, configuration: &Configuration) -> Result<(Solution<i64>, Duration), String> {
+    if configuration.algorithm == Algorithm::CostScalingPushRelabel {
+        let start = Instant::now();
+        let solution = solve_cost_scaling(problem, &configuration.cost_scaling)?;
+        return Ok((solution, start.elapsed()));
+    }
+    let mut solver = MinCostFlow::from_problem(problem.clone());
+    let start = Instant::now();
+    solver.solve_with(configuration.algorithm);
+    let time = start.elapsed();
+    Ok((solver.solution().clone(), time))
+}
+
+fn run(instances: &[Instance], configurations: &[Configuration], repeat: usize) -> Result<(), String> {
+    let width = configurations.iter().map(|c| c.name.len()).max().unwrap_or(0).max("configuration".len());
+    let instance_width = instances.iter().map(|i| i.name.len()).max().unwrap_or(0).max("instance".len());
+    println!("{:iw$}  {:w$}  {:12}  {:>20}  {:>12}  {:>7}", "instance", "configuration", "status", "cost", "time[ms]", "ratio", iw = instance_width, w = width);
+
+    let mut failures = 0;
+    // (time, ratio to the fastest) of each configuration on each instance
+    let mut times = vec![Vec::new(); configurations.len()];
+    for instance in instances.iter() {
+        let mut results = Vec::new();
+        for configuration in configurations.iter() {
+            let mut best: Option<(Solution<i64>, Duration)> = None;
+            for _ in 0..repeat {
+                let (solution, time) = solve(&instance.problem, configuration)?;
+                if best.as_ref().is_none_or(|(_, t)| time < *t) {
+                    best = Some((solution, time));
+                }
+            }
+            results.push(best.unwrap());
+        }
+
+        let fastest = results.iter().map(|(_, t)| *t).min().unwrap_or_default().max(Duration::from_nanos(1));
+        let reference = &results[0].0;
+        for (k, (solution, time)) in results.iter().enumerate() {
+            let mut remark = String::new();
+            if solution.status == Status::Optimal {
+                if let Err(e) = instance.problem.check_solution(solution) {
+                    remark = format!("  WRONG: {}", e);
+                }
+            }
+            if remark.is_empty() && (solution.status != reference.status || solution.cost != reference.cost) {
+                remark = format!("  MISMATCH with {}", configurations[0].name);
+            }
+            if !remark.is_empty() {
+                failures += 1;
+            }
+
+            let ratio = time.as_secs_f64() / fastest.as_secs_f64();
+            let cost = solution.cost.map_or("-".to_string(), |c| c.to_string());
+            println!("{:iw$}  {:w$}  {:12}  {:>20}  {:>12.3}  {:>7.2}{}", instance.name, configurations[k].name, format!("{:?}", solution.status), cost, time.as_secs_f64() * 1e3, ratio, remark, iw = instance_width, w = width);
+            times[k].push((*time, ratio));
+        }
+        println!();
+    }
+
+    // the geometric mean of the ratios compares configurations independently of the instance sizes
+    println!("{:w$}  {:>12}  {:>12}", "configuration", "total[ms]", "mean ratio", w = width);
+    for (k, configuration) in configurations.iter().enumerate() {
+        let total: f64 = times[k].iter().map(|(t, _)| t.as_secs_f64() * 1e3).sum();
+        let mean = (times[k].iter().map(|(_, r)| r.ln()).sum::<f64>() / times[k].len().max(1) as f64).exp();
+        println!("{:w$}  {:>12.3}  {:>12.2}", configuration.name, total, mean, w = width);
+    }
+
+    if failures > 0 {
+        return Err(format!("{} wrong or mismatching results", failures));
+    }
+    Ok(())
+}
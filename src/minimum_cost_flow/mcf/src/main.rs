@@ -0,0 +1,442 @@
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use generator::{goto, gridgen, netgen, road};
+use min_cost_flow::{Algorithm, Flow, MinCostFlow, MinCostFlowProblem, MinCostFlowSolver, Solution, Status};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+mod bench;
+
+const USAGE: &str = "usage: mcf [solve] [options] [input]
+       mcf netgen [-o <file>] [input]
+       mcf gridgen [-o <file>] <seed> <width> <height> <arcs> <sources> <sinks> <supply> <min_cost> <max_cost> <min_capacity> <max_capacity>
+       mcf goto [-o <file>] <seed> <width> <height> <degree> <max_capacity> <max_cost>
+       mcf road [-o <file>] <seed> <width> <height> <highway_spacing> <capacity> <pairs> <supply_per_pair>
+       mcf bench [options] [input...]    (see mcf bench --help)
+
+reads a minimum cost flow problem from input (stdin if omitted or -) and writes the solution
+
+options:
+  -i, --input-format <dimacs|lgf|json>   default: by the extension of input (.lgf, .json), otherwise dimacs
+  -f, --output-format <dimacs|lgf|json>  default: dimacs
+  -o, --output <file>                    default: stdout
+  -t, --type <i32|i64|i128>              number type of capacities and costs. default: i64
+  -s, --solver <name>                    auto, cost-scaling, network-simplex, successive-shortest-path,
+                                         capacity-scaling, relaxation, cycle-canceling. default: auto
+  -p, --potentials                       write the potentials
+      --stats                            write the algorithm, time and cost to stderr
+  -h, --help                             show this message
+
+cost scaling options:
+      --alpha <n>                        scaling factor (>= 2). default: 5
+      --look-ahead <on|off>              default: on
+      --price-update <on|off>            default: off
+      --price-refinement <on|off>        default: off
+      --no-feasibility-check             skip the feasibility check before solving
+
+netgen reads the input of the NETGEN generator (seed, problem number and 13 parameters, repeated until
+a seed <= 0) and writes the generated networks in the DIMACS format
+gridgen, goto and road write a grid, grid on torus or road-like network in the DIMACS format
+
+exit status:
+  0 optimal, 1 infeasible, 2 unbalanced, 3 not solved (e.g. BadCostRange), 4 invalid arguments or input";
+
+const EXIT_OPTIMAL: i32 = 0;
+const EXIT_INFEASIBLE: i32 = 1;
+const EXIT_UNBALANCED: i32 = 2;
+const EXIT_NOT_SOLVED: i32 = 3;
+const EXIT_ERROR: i32 = 4;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Dimacs,
+    Lgf,
+    Json,
+}
+
+#[derive(Copy, Clone)]
+enum FlowType {
+    I32,
+    I64,
+    I128,
+}
+
+struct Options {
+    input: Option<String>,
+    output: Option<String>,
+    input_format: Option<Format>,
+    output_format: Format,
+    flow_type: FlowType,
+    algorithm: Option<Algorithm>, // None: chosen by MinCostFlow
+    cost_scaling: CostScalingOptions,
+    potentials: bool,
+    statistics: bool,
+}
+
+#[derive(Copy, Clone)]
+struct CostScalingOptions {
+    alpha: Option<i64>,
+    check_feasibility: bool,
+    look_ahead: bool,
+    price_update: bool,
+    price_refinement: bool,
+}
+
+impl Default for CostScalingOptions {
+    fn default() -> Self {
+        CostScalingOptions { alpha: None, check_feasibility: true, look_ahead: true, price_update: false, price_refinement: false }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match args.first().map(|a| a.as_str()) {
+        Some("solve") => &args[1..],
+        Some("netgen") => match netgen(&args[1..]) {
+            Ok(()) => process::exit(EXIT_OPTIMAL),
+            Err(e) => {
+                eprintln!("mcf: {}", e);
+                process::exit(EXIT_ERROR);
+            }
+        },
+        Some("bench") => {
+            if args.iter().any(|a| a == "-h" || a == "--help") {
+                println!("{}", bench::USAGE);
+                return;
+            }
+            match bench::bench(&args[1..]) {
+                Ok(()) => process::exit(EXIT_OPTIMAL),
+                Err(e) => {
+                    eprintln!("mcf: {}", e);
+                    process::exit(EXIT_ERROR);
+                }
+            }
+        }
+        Some(family @ "gridgen") | Some(family @ "goto") | Some(family @ "road") => match generate(family, &args[1..]) {
+            Ok(()) => process::exit(EXIT_OPTIMAL),
+            Err(e) => {
+                eprintln!("mcf: {}\n\n{}", e, USAGE);
+                process::exit(EXIT_ERROR);
+            }
+        },
+        _ => &args[..],
+    };
+
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("mcf: {}\n\n{}", e, USAGE);
+            process::exit(EXIT_ERROR);
+        }
+    };
+
+    let result = match options.flow_type {
+        FlowType::I32 => run::<i32>(&options),
+        FlowType::I64 => run::<i64>(&options),
+        FlowType::I128 => run::<i128>(&options),
+    };
+    match result {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("mcf: {}", e);
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        input: None,
+        output: None,
+        input_format: None,
+        output_format: Format::Dimacs,
+        flow_type: FlowType::I64,
+        algorithm: None,
+        cost_scaling: CostScalingOptions::default(),
+        potentials: false,
+        statistics: false,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-i" | "--input-format" => options.input_format = Some(parse_format(value(&mut iter, arg)?)?),
+            "-f" | "--output-format" => options.output_format = parse_format(value(&mut iter, arg)?)?,
+            "-o" | "--output" => options.output = Some(value(&mut iter, arg)?.to_string()),
+            "-t" | "--type" => {
+                options.flow_type = match value(&mut iter, arg)? {
+                    "i32" => FlowType::I32,
+                    "i64" => FlowType::I64,
+                    "i128" => FlowType::I128,
+                    t => return Err(format!("unknown type '{}'", t)),
+                }
+            }
+            "-s" | "--solver" => options.algorithm = parse_solver(value(&mut iter, arg)?)?,
+            "-p" | "--potentials" => options.potentials = true,
+            "--stats" => options.statistics = true,
+            "--alpha" => {
+                let alpha = value(&mut iter, arg)?;
+                match alpha.parse() {
+                    Ok(alpha) if alpha >= 2 => options.cost_scaling.alpha = Some(alpha),
+                    _ => return Err(format!("alpha must be an integer >= 2 but is '{}'", alpha)),
+                }
+            }
+            "--look-ahead" => options.cost_scaling.look_ahead = parse_switch(value(&mut iter, arg)?)?,
+            "--price-update" => options.cost_scaling.price_update = parse_switch(value(&mut iter, arg)?)?,
+            "--price-refinement" => options.cost_scaling.price_refinement = parse_switch(value(&mut iter, arg)?)?,
+            "--no-feasibility-check" => options.cost_scaling.check_feasibility = false,
+            "-" => options.input = None,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => {
+                if options.input.is_some() {
+                    return Err("more than one input".to_string());
+                }
+                options.input = Some(arg.clone());
+            }
+        }
+    }
+    Ok(options)
+}
+
+fn netgen(args: &[String]) -> Result<(), String> {
+    let mut input = None;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(value(&mut iter, arg)?.to_string()),
+            "-" => input = None,
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => input = Some(arg.clone()),
+        }
+    }
+
+    let name = input.as_deref().unwrap_or("<stdin>");
+    let reader: Box<dyn BufRead> = match input.as_ref() {
+        Some(path) => Box::new(BufReader::new(File::open(path).map_err(|e| format!("{}: {}", name, e))?)),
+        None => Box::new(BufReader::new(io::stdin())),
+    };
+    let problems = netgen::read_parameters(reader).map_err(|e| format!("{}: {}", name, e))?;
+
+    let mut writer: Box<dyn Write> = match output.as_ref() {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|e| format!("{}: {}", path, e))?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    for (seed, problem, parameters) in problems {
+        let network = netgen::netgen(seed, &parameters).map_err(|e| e.to_string())?;
+        network.write_dimacs(problem, &mut writer).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+fn generate(family: &str, args: &[String]) -> Result<(), String> {
+    let mut output = None;
+    let mut values = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(value(&mut iter, arg)?.to_string()),
+            _ => values.push(arg.parse::<i64>().map_err(|_| format!("invalid parameter '{}'", arg))?),
+        }
+    }
+    let expected = match family {
+        "gridgen" => 11,
+        "goto" => 6,
+        _ => 7,
+    };
+    if values.len() != expected {
+        return Err(format!("{} needs {} parameters but got {}", family, expected, values.len()));
+    }
+
+    let v = &values;
+    let problem = match family {
+        "gridgen" => {
+            let parameters = gridgen::GridgenParameters {
+                width: size(v[1])?,
+                height: size(v[2])?,
+                arcs: size(v[3])?,
+                sources: size(v[4])?,
+                sinks: size(v[5])?,
+                supply: v[6],
+                min_cost: v[7],
+                max_cost: v[8],
+                min_capacity: v[9],
+                max_capacity: v[10],
+            };
+            gridgen::gridgen(v[0], &parameters)
+        }
+        "goto" => goto::goto(v[0], &goto::GotoParameters { width: size(v[1])?, height: size(v[2])?, degree: size(v[3])?, max_capacity: v[4], max_cost: v[5] }),
+        _ => road::road(v[0], &road::RoadParameters { width: size(v[1])?, height: size(v[2])?, highway_spacing: size(v[3])?, capacity: v[4], pairs: size(v[5])?, supply_per_pair: v[6] }),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let mut writer: Box<dyn Write> = match output.as_ref() {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|e| format!("{}: {}", path, e))?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    let parameters: Vec<String> = values.iter().map(|x| x.to_string()).collect();
+    writeln!(writer, "c {} {}", family, parameters.join(" ")).map_err(|e| e.to_string())?;
+    dimacs::write_problem(&problem, &mut writer).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+fn size(x: i64) -> Result<usize, String> {
+    usize::try_from(x).map_err(|_| format!("{} must be non-negative", x))
+}
+
+fn value<'a>(iter: &mut std::slice::Iter<'a, String>, option: &str) -> Result<&'a str, String> {
+    match iter.next() {
+        Some(value) => Ok(value),
+        None => Err(format!("missing value of {}", option)),
+    }
+}
+
+fn parse_format(format: &str) -> Result<Format, String> {
+    match format {
+        "dimacs" => Ok(Format::Dimacs),
+        "lgf" => Ok(Format::Lgf),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("unknown format '{}'", format)),
+    }
+}
+
+fn parse_solver(solver: &str) -> Result<Option<Algorithm>, String> {
+    match solver {
+        "auto" => Ok(None),
+        "cost-scaling" => Ok(Some(Algorithm::CostScalingPushRelabel)),
+        "network-simplex" => Ok(Some(Algorithm::NetworkSimplex)),
+        "successive-shortest-path" => Ok(Some(Algorithm::SuccessiveShortestPath)),
+        "capacity-scaling" => Ok(Some(Algorithm::CapacityScaling)),
+        "relaxation" => Ok(Some(Algorithm::Relaxation)),
+        "cycle-canceling" => Ok(Some(Algorithm::MinimumMeanCycleCanceling)),
+        _ => Err(format!("unknown solver '{}'", solver)),
+    }
+}
+
+fn parse_switch(switch: &str) -> Result<bool, String> {
+    match switch {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(format!("expected on or off but got '{}'", switch)),
+    }
+}
+
+fn run<F: Flow + Serialize + DeserializeOwned>(options: &Options) -> Result<i32, String> {
+    let problem = read_problem::<F>(options)?;
+    let mut solver = MinCostFlow::from_problem(problem);
+
+    let decision = if options.algorithm.is_none() { Some(solver.choose_algorithm()) } else { None };
+    let algorithm = match decision.as_ref() {
+        Some(decision) => decision.algorithm,
+        None => options.algorithm.unwrap(),
+    };
+
+    let start = Instant::now();
+    let mut solution = if algorithm == Algorithm::CostScalingPushRelabel {
+        solve_cost_scaling(solver.problem(), &options.cost_scaling)?
+    } else {
+        solver.solve_with(algorithm);
+        solver.solution().clone()
+    };
+    let time = start.elapsed();
+
+    if !options.potentials {
+        solution.potentials.clear();
+    }
+
+    if options.statistics {
+        match decision.as_ref() {
+            Some(decision) => eprintln!("algorithm: {}", decision),
+            None => eprintln!("algorithm: {:?} nodes:{} edges:{}", algorithm, solver.problem().num_of_nodes, solver.problem().num_of_edges()),
+        }
+        eprintln!("time: {}.{:03}", time.as_secs(), time.subsec_millis());
+        eprintln!("status: {:?}", solution.status);
+        if let Some(cost) = solution.cost {
+            eprintln!("cost: {}", cost);
+        }
+    }
+
+    write_solution(solver.problem(), &solution, options).map_err(|e| e.to_string())?;
+
+    Ok(match solution.status {
+        Status::Optimal => EXIT_OPTIMAL,
+        Status::Infeasible => EXIT_INFEASIBLE,
+        Status::Unbalanced => EXIT_UNBALANCED,
+        _ => EXIT_NOT_SOLVED,
+    })
+}
+
+fn solve_cost_scaling<F: Flow>(problem: &MinCostFlowProblem<F>, options: &CostScalingOptions) -> Result<Solution<F>, String> {
+    let (mut solver, edge_ids) = problem.build::<CostScalingPushRelabel<F>>();
+    if let Some(alpha) = options.alpha {
+        match F::from_i64(alpha) {
+            Some(alpha) => solver.set_alpha(alpha),
+            None => return Err(format!("alpha {} is out of range", alpha)),
+        }
+    }
+    solver.set_check_feasibility(options.check_feasibility);
+    solver.use_look_ahead_heuristic(options.look_ahead);
+    solver.use_price_update_heuristic(options.price_update);
+    solver.use_price_refinement_heuristic(options.price_refinement);
+
+    // the trait makes the potentials exact
+    MinCostFlowSolver::solve(&mut solver);
+    Ok(Solution::from_solver(&solver, &edge_ids))
+}
+
+fn read_problem<F: Flow + DeserializeOwned>(options: &Options) -> Result<MinCostFlowProblem<F>, String> {
+    let name = options.input.as_deref().unwrap_or("<stdin>");
+    let format = match (options.input_format, options.input.as_ref()) {
+        (Some(format), _) => format,
+        (None, Some(path)) => match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("lgf") => Format::Lgf,
+            Some("json") => Format::Json,
+            _ => Format::Dimacs,
+        },
+        (None, None) => Format::Dimacs,
+    };
+
+    let reader: Box<dyn BufRead> = match options.input.as_ref() {
+        Some(path) => Box::new(BufReader::new(File::open(path).map_err(|e| format!("{}: {}", name, e))?)),
+        None => Box::new(BufReader::new(io::stdin())),
+    };
+
+    let problem = match format {
+        Format::Dimacs => dimacs::read_problem(reader).map_err(|e| e.to_string()),
+        Format::Lgf => lgf::read_problem(reader).map_err(|e| e.to_string()),
+        Format::Json => serde_json::from_reader(reader).map_err(|e| e.to_string()),
+    };
+    problem.map_err(|e| format!("{}: {}", name, e))
+}
+
+// only the JSON output has a solution that is not optimal. the others write the status as a comment
+fn write_solution<F: Flow + Serialize>(problem: &MinCostFlowProblem<F>, solution: &Solution<F>, options: &Options) -> io::Result<()> {
+    let mut writer: Box<dyn Write> = match options.output.as_ref() {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+
+    let optimal = solution.status == Status::Optimal;
+    match options.output_format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut writer, solution)?;
+            writeln!(writer)?;
+        }
+        Format::Dimacs if optimal => dimacs::write_solution(problem, solution, options.potentials, &mut writer)?,
+        Format::Lgf if optimal => lgf::write_solution(problem, solution, &mut writer)?,
+        Format::Dimacs => writeln!(writer, "c {:?}", solution.status)?,
+        Format::Lgf => writeln!(writer, "# {:?}", solution.status)?,
+    }
+    writer.flush()
+}
@@ -0,0 +1,291 @@
+#![allow(clippy::needless_range_loop)]
+use capacity_scaling::CapacityScaling;
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use minimum_mean_cycle_canceling::MinimumMeanCycleCanceling;
+use network_simplex::NetworkSimplex;
+use relaxation::Relaxation;
+use std::collections::VecDeque;
+use std::fmt;
+use successive_shortest_path::SuccessiveShortestPath;
+
+pub use minimum_cost_flow_common::{CostRange, Edge, EdgeId, Flow, FlowPath, MinCostFlowProblem, MinCostFlowSolver, ProblemEdge, Solution, Status};
+
+// networks with at most this many nodes are solved by the network simplex
+const MID_SIZE_NODES: usize = 50_000;
+// capacity scaling is used when the capacities are at least this large and the network is sparse
+const LARGE_CAPACITY: i128 = 1 << 20;
+const SPARSE_FACTOR: usize = 4;
+// cost scaling multiplies the costs by alpha * n
+const COST_SCALING_ALPHA: i128 = 5;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Algorithm {
+    CostScalingPushRelabel,
+    NetworkSimplex,
+    SuccessiveShortestPath,
+    CapacityScaling,
+    Relaxation,
+    MinimumMeanCycleCanceling,
+}
+
+// features of the network used to choose an algorithm (computed in i128 to avoid overflow)
+#[derive(Clone, Debug)]
+pub struct Statistics {
+    pub num_of_nodes: usize,
+    pub num_of_edges: usize,
+    pub total_supply: i128, // sum of the positive excesses after the lower bounds are sent
+    pub gamma: i128,        // maximum absolute value of any edge cost
+    pub min_capacity: i128, // minimum of upper - lower
+    pub max_capacity: i128, // maximum of upper - lower
+    pub has_negative_cost: bool,
+    pub has_lower_bound: bool,
+    pub is_bipartite: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Decision {
+    pub algorithm: Algorithm,
+    pub reason: &'static str,
+    pub statistics: Statistics,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = &self.statistics;
+        write!(
+            f,
+            "{:?} ({}) nodes:{} edges:{} total_supply:{} gamma:{} capacity:[{}, {}] bipartite:{}",
+            self.algorithm, self.reason, s.num_of_nodes, s.num_of_edges, s.total_supply, s.gamma, s.min_capacity, s.max_capacity, s.is_bipartite
+        )
+    }
+}
+
+// collects a network and solves it with the algorithm that fits it best
+pub struct MinCostFlow<F: Flow> {
+    problem: MinCostFlowProblem<F>,
+    solution: Solution<F>,
+}
+
+impl<F: Flow> MinCostFlow<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        MinCostFlow::from_problem(MinCostFlowProblem::new(num_of_nodes))
+    }
+
+    pub fn from_problem(problem: MinCostFlowProblem<F>) -> Self {
+        MinCostFlow { problem, solution: Solution { status: Status::NotSolved, flows: Vec::new(), potentials: Vec::new(), cost: None } }
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        self.problem.add_directed_edge(from, to, lower, upper, cost)
+    }
+
+    pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        let e = &self.problem.edges[edge_id.index()];
+        let flow = if self.solution.status == Status::Optimal { self.solution.flows[edge_id.index()] } else { F::zero() };
+        Edge::new(e.from, e.to, flow, e.lower, e.upper, e.cost)
+    }
+
+    pub fn get_potential(&self) -> Vec<F> {
+        if self.solution.status == Status::Optimal {
+            self.solution.potentials.clone()
+        } else {
+            vec![F::zero(); self.problem.num_of_nodes]
+        }
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.problem.add_supply(node, supply);
+    }
+
+    pub fn optimal_cost(&self) -> Option<i128> {
+        self.solution.cost
+    }
+
+    pub fn problem(&self) -> &MinCostFlowProblem<F> {
+        &self.problem
+    }
+
+    pub fn solution(&self) -> &Solution<F> {
+        &self.solution
+    }
+
+    // how much the cost of each edge can change before the optimal flow changes
+    pub fn cost_ranges(&self) -> Vec<CostRange> {
+        self.problem.cost_ranges(&self.solution)
+    }
+
+    // the optimal flow as paths and cycles
+    pub fn decompose(&self) -> Vec<FlowPath<F>> {
+        self.problem.decompose(&self.solution)
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        // the lower bounds are sent first, and the rest is what the solvers have to ship
+        let mut excess: Vec<i128> = self.problem.supply.iter().map(|b| F::to_i128(b).unwrap()).collect();
+        for e in self.problem.edges.iter() {
+            excess[e.from] -= F::to_i128(&e.lower).unwrap();
+            excess[e.to] += F::to_i128(&e.lower).unwrap();
+        }
+        let total_supply = excess.iter().map(|&b| i128::max(b, 0)).sum();
+
+        let mut gamma = 0;
+        let mut min_capacity = 0;
+        let mut max_capacity = 0;
+        let mut has_negative_cost = false;
+        let mut has_lower_bound = false;
+        for (i, e) in self.problem.edges.iter().enumerate() {
+            let cost = F::to_i128(&e.cost).unwrap();
+            let capacity = F::to_i128(&e.upper).unwrap() - F::to_i128(&e.lower).unwrap();
+            gamma = i128::max(gamma, cost.abs());
+            min_capacity = if i == 0 { capacity } else { i128::min(min_capacity, capacity) };
+            max_capacity = i128::max(max_capacity, capacity);
+            has_negative_cost |= e.cost < F::zero();
+            has_lower_bound |= e.lower != F::zero();
+        }
+
+        Statistics { num_of_nodes: self.problem.num_of_nodes, num_of_edges: self.problem.num_of_edges(), total_supply, gamma, min_capacity, max_capacity, has_negative_cost, has_lower_bound, is_bipartite: self.is_bipartite() }
+    }
+
+    pub fn choose_algorithm(&self) -> Decision {
+        let statistics = self.statistics();
+        let s = &statistics;
+        let n = s.num_of_nodes as i128;
+        let m = s.num_of_edges;
+
+        // the network simplex and cost scaling return BadCostRange unless these fit in F
+        // network simplex: the artificial edges cost (gamma + 1) * n, and the potentials up to 6 times that.
+        // it also panics unless upper - lower fits
+        // cost scaling: the scaled costs are gamma * alpha * n, and the reduced costs up to (12 * n + 2) times that.
+        // the excesses are bounded by the supplies plus 2 * |lower| + |upper| of the edges other than self loops
+        let network_simplex_fits = fits::<F>(&[6, s.gamma.saturating_add(1), n]) && fits::<F>(&[s.max_capacity]);
+        let cost_scaling_fits = fits::<F>(&[i128::max(s.gamma, 1), COST_SCALING_ALPHA, n, 12 * n + 2]) && self.load().is_some_and(|load| fits::<F>(&[load]));
+
+        let (algorithm, reason) = if !fits::<F>(&[s.gamma, COST_SCALING_ALPHA, n]) {
+            (Algorithm::SuccessiveShortestPath, "costs are too large to be scaled")
+        } else if s.total_supply <= n && !s.has_negative_cost {
+            (Algorithm::SuccessiveShortestPath, "small total supply")
+        } else if s.is_bipartite && !s.has_lower_bound {
+            (Algorithm::Relaxation, "bipartite network")
+        } else if s.max_capacity >= LARGE_CAPACITY && m <= SPARSE_FACTOR * s.num_of_nodes {
+            (Algorithm::CapacityScaling, "large capacities on a sparse network")
+        } else if s.num_of_nodes <= MID_SIZE_NODES && network_simplex_fits {
+            (Algorithm::NetworkSimplex, "mid-sized network")
+        } else if s.num_of_nodes <= MID_SIZE_NODES {
+            (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for the network simplex")
+        } else if cost_scaling_fits {
+            (Algorithm::CostScalingPushRelabel, "large network")
+        } else {
+            (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for cost scaling")
+        };
+
+        Decision { algorithm, reason, statistics }
+    }
+
+    // solve with the algorithm chosen by choose_algorithm
+    pub fn solve_auto(&mut self) -> (Status, Decision) {
+        let decision = self.choose_algorithm();
+        let status = self.solve_with(decision.algorithm);
+        (status, decision)
+    }
+
+    pub fn solve_with(&mut self, algorithm: Algorithm) -> Status {
+        match algorithm {
+            Algorithm::CostScalingPushRelabel => self.run::<CostScalingPushRelabel<F>>(),
+            Algorithm::NetworkSimplex => self.run::<NetworkSimplex<F>>(),
+            Algorithm::SuccessiveShortestPath => self.run::<SuccessiveShortestPath<F>>(),
+            Algorithm::CapacityScaling => self.run::<CapacityScaling<F>>(),
+            Algorithm::Relaxation => self.run::<Relaxation<F>>(),
+            Algorithm::MinimumMeanCycleCanceling => self.run::<MinimumMeanCycleCanceling<F>>(),
+        }
+    }
+
+    fn run<S: MinCostFlowSolver<F>>(&mut self) -> Status {
+        self.solution = self.problem.solve::<S>();
+        self.solution.status
+    }
+
+    fn load(&self) -> Option<i128> {
+        let abs = |x: &F| F::to_i128(x).unwrap().checked_abs();
+        let mut load: i128 = 0;
+        for s in self.problem.supply.iter() {
+            load = load.checked_add(abs(s)?)?;
+        }
+        for e in self.problem.edges.iter().filter(|e| e.from != e.to) {
+            load = load.checked_add(abs(&e.lower)?.checked_mul(2)?)?.checked_add(abs(&e.upper)?)?;
+        }
+        Some(load)
+    }
+
+    // whether the underlying undirected graph is 2-colorable
+    fn is_bipartite(&self) -> bool {
+        let num_of_nodes = self.problem.num_of_nodes;
+        let mut graph = vec![vec![]; num_of_nodes];
+        for e in self.problem.edges.iter() {
+            graph[e.from].push(e.to);
+            graph[e.to].push(e.from);
+        }
+
+        let mut color = vec![None; num_of_nodes];
+        let mut que = VecDeque::new();
+        for s in 0..num_of_nodes {
+            if color[s].is_some() {
+                continue;
+            }
+            color[s] = Some(false);
+            que.push_back(s);
+            while let Some(u) = que.pop_front() {
+                let c = color[u].unwrap();
+                for &v in graph[u].iter() {
+                    match color[v] {
+                        Some(d) if d == c => return false,
+                        Some(_) => {}
+                        None => {
+                            color[v] = Some(!c);
+                            que.push_back(v);
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+// whether the product of the factors fits in F
+fn fits<F: Flow>(factors: &[i128]) -> bool {
+    factors.iter().try_fold(1i128, |p, &x| p.checked_mul(x)).is_some_and(|p| F::from_i128(p).is_some())
+}
+
+impl<F: Flow> MinCostFlowSolver<F> for MinCostFlow<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        MinCostFlow::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        MinCostFlow::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        MinCostFlow::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        self.solve_auto().0
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        MinCostFlow::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        MinCostFlow::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        MinCostFlow::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.solution.status
+    }
+}
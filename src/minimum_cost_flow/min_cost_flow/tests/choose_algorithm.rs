@@ -0,0 +1,103 @@
+use min_cost_flow::{Algorithm, MinCostFlow, Status};
+
+// 0 -> 1 -> ... -> n - 1 with supply from 0 to n - 1, and 0 -> 2 closes an odd cycle
+fn chain(n: usize, cost: i64, capacity: i64, supply: i64, odd_cycle: bool) -> MinCostFlow<i64> {
+    let mut network = MinCostFlow::new(n);
+    for u in 0..n - 1 {
+        network.add_directed_edge(u, u + 1, 0, capacity, cost);
+    }
+    if odd_cycle {
+        network.add_directed_edge(0, 2, 0, capacity, cost);
+    }
+    network.add_supply(0, supply);
+    network.add_supply(n - 1, -supply);
+    network
+}
+
+fn decision(network: &MinCostFlow<i64>) -> (Algorithm, &'static str) {
+    let decision = network.choose_algorithm();
+    (decision.algorithm, decision.reason)
+}
+
+#[test]
+fn chooses_by_shape() {
+    assert_eq!(decision(&chain(30, 1, 100, 5, true)), (Algorithm::SuccessiveShortestPath, "small total supply"));
+    assert_eq!(decision(&chain(30, 1, 100, 100, false)), (Algorithm::Relaxation, "bipartite network"));
+    assert_eq!(decision(&chain(30, 1, 1 << 20, 100, true)), (Algorithm::CapacityScaling, "large capacities on a sparse network"));
+    assert_eq!(decision(&chain(30, 1, 100, 100, true)), (Algorithm::NetworkSimplex, "mid-sized network"));
+    assert_eq!(decision(&chain(50_001, 1, 100_000, 100_000, true)), (Algorithm::CostScalingPushRelabel, "large network"));
+
+    // relaxation does not take lower bounds, and negative costs need more than a small total supply
+    let mut network = chain(30, 1, 100, 100, false);
+    network.add_directed_edge(0, 1, 1, 2, 1);
+    assert_eq!(decision(&network), (Algorithm::NetworkSimplex, "mid-sized network"));
+    let mut network = chain(30, 1, 100, 5, true);
+    network.add_directed_edge(0, 1, 0, 2, -1);
+    assert_eq!(decision(&network), (Algorithm::NetworkSimplex, "mid-sized network"));
+
+    // the lower bounds count towards the total supply
+    let mut network = chain(30, 1, 100, 5, true);
+    network.add_directed_edge(29, 0, 2, 3, 1);
+    assert_eq!(network.statistics().total_supply, 7);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "small total supply"));
+    let mut network = chain(30, 1, 100, 5, true);
+    network.add_directed_edge(4, 3, 40, 50, 1);
+    assert_eq!(network.statistics().total_supply, 45);
+    assert_eq!(decision(&network), (Algorithm::NetworkSimplex, "mid-sized network"));
+    assert_eq!(network.solve_auto().0, Status::Optimal);
+    // a negative lower bound sends the other way, and a self loop sends nothing
+    let mut network = chain(30, 1, 100, 5, true);
+    network.add_directed_edge(0, 29, -40, 0, 1);
+    network.add_directed_edge(5, 5, 40, 50, 1);
+    assert_eq!(network.statistics().total_supply, 45);
+}
+
+#[test]
+fn falls_back_when_costs_are_too_large() {
+    // gamma * alpha * n does not fit
+    let network = chain(30, i64::MAX / 149, 100, 100, true);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs are too large to be scaled"));
+
+    // the network simplex needs 6 * (gamma + 1) * n
+    let n = 30;
+    let mut network = chain(n, (i64::MAX / (6 * n as i64)) - 1, 100, 100, true);
+    assert_eq!(decision(&network), (Algorithm::NetworkSimplex, "mid-sized network"));
+    assert_eq!(network.solve_auto().0, Status::Optimal);
+
+    let mut network = chain(n, i64::MAX / (6 * n as i64), 100, 100, true);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for the network simplex"));
+    assert_eq!(network.solve_with(Algorithm::NetworkSimplex), Status::BadCostRange);
+    assert_eq!(network.solve_auto().0, Status::Optimal);
+
+    let mut network = chain(n, i64::MAX / (5 * n as i64), 100, 100, true);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for the network simplex"));
+    assert_eq!(network.solve_auto().0, Status::Optimal);
+
+    // cost scaling needs gamma * alpha * n * (12 * n + 2)
+    let n = 50_001;
+    let gamma = i64::MAX / (5 * n as i64 * (12 * n as i64 + 2));
+    assert_eq!(decision(&chain(n, gamma, 100_000, 100_000, true)), (Algorithm::CostScalingPushRelabel, "large network"));
+    let mut network = chain(n, gamma + 1, 100_000, 100_000, true);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for cost scaling"));
+    assert_eq!(network.solve_with(Algorithm::CostScalingPushRelabel), Status::BadCostRange);
+}
+
+#[test]
+fn falls_back_when_capacities_are_too_large() {
+    // the excesses of cost scaling are bounded by the supplies and the capacities of all the edges
+    let n = 50_001;
+    let mut network = chain(n, 1, 100_000, i64::MAX / 2, true);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for cost scaling"));
+    assert_eq!(network.solve_with(Algorithm::CostScalingPushRelabel), Status::BadCostRange);
+
+    // the network simplex keeps the flows relative to lower, so upper - lower must fit. the parallel edges make
+    // the network too dense for capacity scaling
+    let mut network = chain(30, 1, 100, 100, true);
+    for _ in 0..100 {
+        network.add_directed_edge(0, 1, 0, 1, 1);
+    }
+    network.add_directed_edge(0, 2, -1, i64::MAX - 1, 1);
+    assert_eq!(decision(&network), (Algorithm::NetworkSimplex, "mid-sized network"));
+    network.add_directed_edge(0, 2, -1, i64::MAX, 1);
+    assert_eq!(decision(&network), (Algorithm::SuccessiveShortestPath, "costs or capacities are too large for the network simplex"));
+}
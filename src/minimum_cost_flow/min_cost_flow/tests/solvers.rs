@@ -0,0 +1,65 @@
+// differential tests of every MinCostFlowSolver against exhaustive enumeration of the flows
+// the networks are tiny (at most 4 nodes, 6 edges and 4 flow values per edge) and have
+// lower bounds (possibly negative), negative costs, self loops and parallel edges
+use capacity_scaling::CapacityScaling;
+use cost_scaling_push_relabel::CostScalingPushRelabel;
+use min_cost_flow::{MinCostFlow, MinCostFlowSolver, Status};
+use minimum_mean_cycle_canceling::MinimumMeanCycleCanceling;
+use network_simplex::NetworkSimplex;
+use relaxation::Relaxation;
+use successive_shortest_path::SuccessiveShortestPath;
+use test_support::{brute_force, random_problem, Random};
+
+// the status, the cost, the flows and the potentials (by check_solution) through the common interface, and the
+// decomposition of the optimal flow
+fn matches_brute_force<S: MinCostFlowSolver<i64>>(seed: u64) {
+    let mut random = Random(seed);
+    for case in 0..5000 {
+        let problem = random_problem(&mut random);
+        let (status, cost) = brute_force(&problem);
+
+        let solution = problem.solve::<S>();
+        assert_eq!(solution.status, status, "case {}: {:?}", case, problem);
+        if status == Status::Optimal {
+            assert_eq!(solution.cost, cost, "case {}: {:?}", case, problem);
+            problem.check_solution(&solution).unwrap_or_else(|e| panic!("case {}: {}: {:?}", case, e, problem));
+            // the paths and cycles of the optimal flow add up to its cost
+            assert_eq!(problem.decompose(&solution).iter().map(|path| path.cost).sum::<i128>(), cost.unwrap(), "case {}: {:?}", case, problem);
+        }
+    }
+}
+
+#[test]
+fn cost_scaling_push_relabel() {
+    matches_brute_force::<CostScalingPushRelabel<i64>>(0x9e3779b97f4a7c15);
+}
+
+#[test]
+fn network_simplex() {
+    matches_brute_force::<NetworkSimplex<i64>>(0xbf58476d1ce4e5b9);
+}
+
+#[test]
+fn successive_shortest_path() {
+    matches_brute_force::<SuccessiveShortestPath<i64>>(0x94d049bb133111eb);
+}
+
+#[test]
+fn capacity_scaling() {
+    matches_brute_force::<CapacityScaling<i64>>(0x2545f4914f6cdd1d);
+}
+
+#[test]
+fn relaxation() {
+    matches_brute_force::<Relaxation<i64>>(0x5851f42d4c957f2d);
+}
+
+#[test]
+fn minimum_mean_cycle_canceling() {
+    matches_brute_force::<MinimumMeanCycleCanceling<i64>>(0x14057b7ef767814f);
+}
+
+#[test]
+fn min_cost_flow() {
+    matches_brute_force::<MinCostFlow<i64>>(0xda942042e4dd58b5);
+}
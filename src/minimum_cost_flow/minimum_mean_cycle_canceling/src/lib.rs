@@ -0,0 +1,342 @@
+#![allow(clippy::needless_range_loop)]
+use push_relabel::LowerBound;
+
+pub use minimum_cost_flow_common::{Edge, EdgeId, Flow, MinCostFlowSolver, Status};
+
+#[derive(Clone)]
+struct InternalEdge<F: Flow> {
+    to: usize,
+    rev: usize, // 逆辺のindex. graph[to][rev]でアクセスできる
+    flow: F,
+    lower: F,
+    upper: F,
+    cost: F,
+}
+
+impl<F: Flow> InternalEdge<F> {
+    pub fn new(to: usize, rev: usize, flow: F, lower: F, upper: F, cost: F) -> Self {
+        InternalEdge { to, rev, flow, lower, upper, cost }
+    }
+
+    pub fn residual_capacity(&self) -> F {
+        self.upper - self.flow
+    }
+}
+
+// start from a feasible flow and cancel a minimum mean cycle of the residual network until none is negative
+// slow but strongly polynomial, intended as a reference implementation
+pub struct MinimumMeanCycleCanceling<F: Flow> {
+    num_of_nodes: usize,
+    graph: Vec<Vec<InternalEdge<F>>>,
+    edges: Vec<(usize, usize)>, // graph[u][i] of each edge in the order of addition
+
+    // Node
+    initial_excess: Vec<F>,
+    potentials: Vec<F>,
+
+    // Edge
+    is_rev: Vec<Vec<bool>>,
+
+    // status
+    status: Status,
+    optimal_cost: Option<i128>,
+    num_cancel: u64,
+}
+
+impl<F: Flow> MinimumMeanCycleCanceling<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        MinimumMeanCycleCanceling {
+            num_of_nodes,
+            graph: vec![vec![]; num_of_nodes],
+            edges: Vec::new(),
+
+            initial_excess: vec![F::zero(); num_of_nodes],
+            potentials: vec![F::zero(); num_of_nodes],
+
+            is_rev: vec![vec![]; num_of_nodes],
+
+            status: Status::NotSolved,
+            optimal_cost: None,
+            num_cancel: 0,
+        }
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        let e = self.graph[from].len();
+        let re = if from == to { e + 1 } else { self.graph[to].len() };
+
+        let e1 = InternalEdge::new(to, re, F::zero(), lower, upper, cost);
+        self.graph[from].push(e1);
+        self.is_rev[from].push(false);
+
+        let e2 = InternalEdge::new(from, e, F::zero(), F::zero(), -lower, -cost);
+        self.graph[to].push(e2);
+        self.is_rev[to].push(true);
+
+        self.edges.push((from, e));
+        EdgeId::new(self.edges.len() - 1)
+    }
+
+    pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        let (u, i) = self.edges[edge_id.index()];
+        let e = &self.graph[u][i];
+        Edge { from: u, to: e.to, flow: e.flow, lower: e.lower, upper: e.upper, cost: e.cost }
+    }
+
+    pub fn get_potential(&self) -> Vec<F> {
+        self.potentials.clone()
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.initial_excess[node] += supply;
+    }
+
+    pub fn solve(&mut self) -> Status {
+        self.status = Status::NotSolved;
+        self.optimal_cost = None;
+
+        if self.num_of_nodes == 0 {
+            self.status = Status::Optimal;
+            self.optimal_cost = Some(0);
+            return Status::Optimal;
+        }
+
+        if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
+            return Status::Unbalanced;
+        }
+
+        if !self.initialize() {
+            self.status = Status::Infeasible;
+            return Status::Infeasible;
+        }
+
+        self.num_cancel = 0;
+        while let Some(cycle) = self.find_minimum_mean_cycle() {
+            self.cancel(&cycle);
+            self.num_cancel += 1;
+        }
+
+        self.update_potential();
+
+        let mut cost = 0;
+        for u in 0..self.num_of_nodes {
+            for (i, edge) in self.graph[u].iter().enumerate() {
+                if !self.is_rev[u][i] {
+                    cost += F::to_i128(&edge.flow).unwrap() * F::to_i128(&edge.cost).unwrap();
+                }
+            }
+        }
+        self.optimal_cost = Some(cost);
+
+        self.status = Status::Optimal;
+        Status::Optimal
+    }
+
+    pub fn optimal_cost(&self) -> Option<i128> {
+        self.optimal_cost
+    }
+
+    pub fn num_cancel(&self) -> u64 {
+        self.num_cancel
+    }
+
+    fn is_unbalanced(&self) -> bool {
+        let mut total = F::zero();
+        for u in 0..self.num_of_nodes {
+            total += self.initial_excess[u];
+        }
+        total != F::zero()
+    }
+
+    // set a feasible flow
+    // returns false if there is no feasible flow
+    fn initialize(&mut self) -> bool {
+        let mut solver = LowerBound::new(self.num_of_nodes);
+        let mut positions = Vec::new();
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                self.graph[u][i].flow = F::zero();
+                if !self.is_rev[u][i] {
+                    let edge = &self.graph[u][i];
+                    solver.add_edge(u, edge.to, edge.lower, edge.upper);
+                    positions.push((u, i));
+                }
+            }
+        }
+        for u in 0..self.num_of_nodes {
+            solver.add_supply(u, self.initial_excess[u]);
+        }
+
+        let flows = match solver.solve() {
+            Some(flows) => flows,
+            None => return false,
+        };
+        for (k, &(u, i)) in positions.iter().enumerate() {
+            self.push_flow(u, i, flows[k]);
+        }
+        true
+    }
+
+    // Karp's algorithm
+    // d[k][v]: minimum cost of a walk with exactly k edges ending at v (the walk can start anywhere)
+    // returns the edges of a minimum mean cycle if its mean cost is negative
+    fn find_minimum_mean_cycle(&self) -> Option<Vec<(usize, usize)>> {
+        let n = self.num_of_nodes;
+        let mut d: Vec<Vec<Option<i128>>> = vec![vec![None; n]; n + 1];
+        let mut prev = vec![vec![(0, 0); n]; n + 1];
+        for v in 0..n {
+            d[0][v] = Some(0);
+        }
+
+        for k in 0..n {
+            for u in 0..n {
+                let du = match d[k][u] {
+                    Some(du) => du,
+                    None => continue,
+                };
+                for (i, edge) in self.graph[u].iter().enumerate() {
+                    if edge.residual_capacity() <= F::zero() {
+                        continue;
+                    }
+                    let new_d = du + F::to_i128(&edge.cost).unwrap();
+                    if d[k + 1][edge.to].is_none_or(|dv| new_d < dv) {
+                        d[k + 1][edge.to] = Some(new_d);
+                        prev[k + 1][edge.to] = (u, i);
+                    }
+                }
+            }
+        }
+
+        // minimum mean = min_v max_k (d[n][v] - d[k][v]) / (n - k)
+        // (numerator, denominator, v)
+        let mut best: Option<(i128, i128, usize)> = None;
+        for v in 0..n {
+            let dn = match d[n][v] {
+                Some(dn) => dn,
+                None => continue,
+            };
+
+            let mut worst: Option<(i128, i128)> = None;
+            for k in 0..n {
+                if let Some(dk) = d[k][v] {
+                    let (a, b) = (dn - dk, (n - k) as i128);
+                    if worst.is_none_or(|(c, e)| a * e > c * b) {
+                        worst = Some((a, b));
+                    }
+                }
+            }
+
+            let (a, b) = worst.unwrap();
+            if best.is_none_or(|(c, e, _)| a * e < c * b) {
+                best = Some((a, b, v));
+            }
+        }
+
+        let (a, _, v) = best?;
+        if a >= 0 {
+            return None;
+        }
+
+        // the walk of n edges ending at v contains a minimum mean cycle
+        let mut walk = vec![v];
+        let mut x = v;
+        for k in (1..=n).rev() {
+            x = prev[k][x].0;
+            walk.push(x);
+        }
+        let mut seen = vec![n + 1; n];
+        for (pos, &u) in walk.iter().enumerate() {
+            if seen[u] <= n {
+                // walk[seen[u]] -> ... -> walk[pos]は逆向きにたどった閉路
+                let mut cycle = Vec::new();
+                for j in (seen[u] + 1..=pos).rev() {
+                    let level = n - j + 1;
+                    cycle.push(prev[level][walk[j - 1]]);
+                }
+                return Some(cycle);
+            }
+            seen[u] = pos;
+        }
+        unreachable!();
+    }
+
+    fn cancel(&mut self, cycle: &[(usize, usize)]) {
+        let mut flow = F::max_value();
+        for &(u, i) in cycle {
+            flow = F::min(flow, self.graph[u][i].residual_capacity());
+        }
+        for &(u, i) in cycle {
+            self.push_flow(u, i, flow);
+        }
+    }
+
+    // shortest distances in the residual network, which has no negative cycle
+    fn update_potential(&mut self) {
+        self.potentials = vec![F::zero(); self.num_of_nodes];
+        for _ in 0..self.num_of_nodes {
+            let mut update = false;
+            for u in 0..self.num_of_nodes {
+                for edge in self.graph[u].iter() {
+                    if edge.residual_capacity() > F::zero() && self.potentials[u] + edge.cost < self.potentials[edge.to] {
+                        self.potentials[edge.to] = self.potentials[u] + edge.cost;
+                        update = true;
+                    }
+                }
+            }
+            if !update {
+                break;
+            }
+        }
+    }
+
+    fn push_flow(&mut self, u: usize, i: usize, flow: F) {
+        if flow == F::zero() {
+            return;
+        }
+
+        let to = self.graph[u][i].to;
+        let rev = self.graph[u][i].rev;
+
+        self.graph[u][i].flow += flow;
+        self.graph[to][rev].flow -= flow;
+    }
+}
+
+impl<F: Flow> MinCostFlowSolver<F> for MinimumMeanCycleCanceling<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        MinimumMeanCycleCanceling::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        MinimumMeanCycleCanceling::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        MinimumMeanCycleCanceling::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        MinimumMeanCycleCanceling::solve(self)
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        MinimumMeanCycleCanceling::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        MinimumMeanCycleCanceling::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        MinimumMeanCycleCanceling::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+}
@@ -0,0 +1,592 @@
+#![allow(clippy::needless_range_loop)]
+
+pub use minimum_cost_flow_common::{Edge, EdgeId, Flow, MinCostFlowSolver, Status};
+
+const STATE_UPPER: i8 = -1;
+const STATE_TREE: i8 = 0;
+const STATE_LOWER: i8 = 1;
+
+const DIR_UP: i8 = 1;
+const DIR_DOWN: i8 = -1;
+
+const NONE: usize = usize::MAX;
+
+// primal network simplex with block search pivoting rule
+// the basis is kept as a strongly feasible spanning tree rooted at an artificial node
+pub struct NetworkSimplex<F: Flow> {
+    num_of_nodes: usize,
+    num_of_edges: usize,
+    supply: Vec<F>,
+
+    // Edge (the first num_of_edges are the input edges, the rest are artificial edges)
+    source: Vec<usize>,
+    target: Vec<usize>,
+    lower: Vec<F>,
+    upper: Vec<F>,
+    cost: Vec<F>,
+    flow: Vec<F>,
+    state: Vec<i8>,
+
+    // spanning tree (Node)
+    root: usize,
+    parent: Vec<usize>,
+    pred: Vec<usize>, // edge between u and parent[u]
+    pred_dir: Vec<i8>,
+    thread: Vec<usize>,
+    rev_thread: Vec<usize>,
+    succ_num: Vec<usize>,
+    last_succ: Vec<usize>,
+    potentials: Vec<F>,
+    dirty_revs: Vec<usize>,
+
+    // pivot
+    block_size: usize,
+    next_edge: usize,
+    in_edge: usize,
+    join: usize,
+    u_in: usize,
+    v_in: usize,
+    u_out: usize,
+    delta: F,
+
+    // status
+    status: Status,
+    optimal_cost: Option<i128>,
+    num_pivot: u64,
+}
+
+impl<F: Flow> NetworkSimplex<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        NetworkSimplex {
+            num_of_nodes,
+            num_of_edges: 0,
+            supply: vec![F::zero(); num_of_nodes],
+
+            source: Vec::new(),
+            target: Vec::new(),
+            lower: Vec::new(),
+            upper: Vec::new(),
+            cost: Vec::new(),
+            flow: Vec::new(),
+            state: Vec::new(),
+
+            root: num_of_nodes,
+            parent: Vec::new(),
+            pred: Vec::new(),
+            pred_dir: Vec::new(),
+            thread: Vec::new(),
+            rev_thread: Vec::new(),
+            succ_num: Vec::new(),
+            last_succ: Vec::new(),
+            potentials: vec![F::zero(); num_of_nodes],
+            dirty_revs: Vec::new(),
+
+            block_size: 0,
+            next_edge: 0,
+            in_edge: 0,
+            join: 0,
+            u_in: 0,
+            v_in: 0,
+            u_out: 0,
+            delta: F::zero(),
+
+            status: Status::NotSolved,
+            optimal_cost: None,
+            num_pivot: 0,
+        }
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+        // the flows are kept relative to lower, up to upper - lower
+        assert!(lower >= F::zero() || upper <= F::max_value() + lower, "upper - lower must be at most F::max_value()");
+
+        self.source.push(from);
+        self.target.push(to);
+        self.lower.push(lower);
+        self.upper.push(upper);
+        self.cost.push(cost);
+        self.num_of_edges += 1;
+
+        EdgeId::new(self.num_of_edges - 1)
+    }
+
+    pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        let e = edge_id.index();
+        // edges added after the solve have no flow
+        let flow = if self.status == Status::Optimal && e < self.flow.len() { self.lower[e] + self.flow[e] } else { F::zero() };
+        Edge { from: self.source[e], to: self.target[e], flow, lower: self.lower[e], upper: self.upper[e], cost: self.cost[e] }
+    }
+
+    pub fn get_potential(&self) -> Vec<F> {
+        self.potentials[..self.num_of_nodes].to_vec()
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.supply[node] += supply;
+    }
+
+    pub fn solve(&mut self) -> Status {
+        self.status = Status::NotSolved;
+        self.optimal_cost = None;
+        self.num_pivot = 0;
+
+        if self.num_of_nodes == 0 {
+            self.status = Status::Optimal;
+            self.optimal_cost = Some(0);
+            return Status::Optimal;
+        }
+
+        if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
+            return Status::Unbalanced;
+        }
+
+        if !self.initialize() {
+            self.status = Status::BadCostRange;
+            return Status::BadCostRange;
+        }
+
+        while self.find_entering_edge() {
+            self.num_pivot += 1;
+            self.find_join_node();
+            let change = self.find_leaving_edge();
+            self.change_flow(change);
+            if change {
+                self.update_tree_structure();
+                self.update_potential();
+            }
+        }
+
+        // 人工辺に流量が残っている
+        let infeasible = (self.num_of_edges..self.num_of_edges + self.num_of_nodes).any(|e| self.flow[e] != F::zero());
+        self.remove_artificial_edges();
+        if infeasible {
+            self.status = Status::Infeasible;
+            return Status::Infeasible;
+        }
+
+        let mut cost = 0;
+        for e in 0..self.num_of_edges {
+            cost += F::to_i128(&(self.lower[e] + self.flow[e])).unwrap() * F::to_i128(&self.cost[e]).unwrap();
+        }
+        self.optimal_cost = Some(cost);
+
+        self.status = Status::Optimal;
+        Status::Optimal
+    }
+
+    pub fn optimal_cost(&self) -> Option<i128> {
+        self.optimal_cost
+    }
+
+    pub fn num_pivot(&self) -> u64 {
+        self.num_pivot
+    }
+
+    fn is_unbalanced(&self) -> bool {
+        let mut total = F::zero();
+        for u in 0..self.num_of_nodes {
+            total += self.supply[u];
+        }
+        total != F::zero()
+    }
+
+    // build the initial strongly feasible tree from artificial edges between each node and the root
+    fn initialize(&mut self) -> bool {
+        let n = self.num_of_nodes;
+        let m = self.num_of_edges;
+
+        // artificial edges must be more expensive than any path of input edges
+        let mut gamma = F::zero();
+        for e in 0..m {
+            gamma = F::max(gamma, if self.cost[e] < F::zero() { -self.cost[e] } else { self.cost[e] });
+        }
+        let art_cost = match (gamma + F::one()).checked_mul(&F::from_usize(n).unwrap()) {
+            Some(c) => c,
+            None => return false,
+        };
+        // potentials stay within a few multiples of art_cost
+        if art_cost.checked_mul(&F::from_usize(6).unwrap()).is_none() {
+            return false;
+        }
+
+        self.flow = vec![F::zero(); m + n];
+        self.state = vec![STATE_LOWER; m + n];
+
+        // 下界の分だけ先に流しておく
+        let mut supply = self.supply.clone();
+        for e in 0..m {
+            supply[self.source[e]] -= self.lower[e];
+            supply[self.target[e]] += self.lower[e];
+        }
+
+        self.parent = vec![NONE; n + 1];
+        self.pred = vec![NONE; n + 1];
+        self.pred_dir = vec![DIR_UP; n + 1];
+        self.thread = vec![0; n + 1];
+        self.rev_thread = vec![0; n + 1];
+        self.succ_num = vec![1; n + 1];
+        self.last_succ = vec![0; n + 1];
+        self.potentials = vec![F::zero(); n + 1];
+
+        let root = self.root;
+        self.thread[root] = 0;
+        self.rev_thread[0] = root;
+        self.succ_num[root] = n + 1;
+        self.last_succ[root] = root - 1;
+
+        for u in 0..n {
+            let e = m + u;
+            self.parent[u] = root;
+            self.pred[u] = e;
+            self.thread[u] = u + 1;
+            self.rev_thread[u + 1] = u;
+            self.last_succ[u] = u;
+            self.state[e] = STATE_TREE;
+            if supply[u] >= F::zero() {
+                self.pred_dir[u] = DIR_UP;
+                self.source.push(u);
+                self.target.push(root);
+                self.flow[e] = supply[u];
+                self.cost.push(F::zero());
+            } else {
+                self.pred_dir[u] = DIR_DOWN;
+                self.potentials[u] = art_cost;
+                self.source.push(root);
+                self.target.push(u);
+                self.flow[e] = -supply[u];
+                self.cost.push(art_cost);
+            }
+        }
+
+        self.block_size = usize::max((m as f64).sqrt().ceil() as usize, 10);
+        self.next_edge = 0;
+        true
+    }
+
+    // so that the edges added after the solve follow the input edges
+    fn remove_artificial_edges(&mut self) {
+        let m = self.num_of_edges;
+        self.source.truncate(m);
+        self.target.truncate(m);
+        self.cost.truncate(m);
+        self.flow.truncate(m);
+        self.state.truncate(m);
+    }
+
+    fn capacity(&self, e: usize) -> F {
+        if e < self.num_of_edges {
+            self.upper[e] - self.lower[e]
+        } else {
+            F::max_value()
+        }
+    }
+
+    fn reduced_cost(&self, e: usize) -> F {
+        self.cost[e] + self.potentials[self.source[e]] - self.potentials[self.target[e]]
+    }
+
+    // block search pivoting rule
+    fn find_entering_edge(&mut self) -> bool {
+        let m = self.num_of_edges;
+        let mut mini = F::zero();
+        let mut count = self.block_size;
+
+        for k in 0..m {
+            let e = (self.next_edge + k) % m;
+            let c = match self.state[e] {
+                STATE_LOWER => self.reduced_cost(e),
+                STATE_UPPER => -self.reduced_cost(e),
+                _ => F::zero(),
+            };
+            if c < mini {
+                mini = c;
+                self.in_edge = e;
+            }
+
+            count -= 1;
+            if count == 0 {
+                if mini < F::zero() {
+                    self.next_edge = (e + 1) % m;
+                    return true;
+                }
+                count = self.block_size;
+            }
+        }
+
+        if mini < F::zero() {
+            self.next_edge = (self.in_edge + 1) % m;
+            return true;
+        }
+        false
+    }
+
+    fn find_join_node(&mut self) {
+        let mut u = self.source[self.in_edge];
+        let mut v = self.target[self.in_edge];
+        while u != v {
+            if self.succ_num[u] < self.succ_num[v] {
+                u = self.parent[u];
+            } else {
+                v = self.parent[v];
+            }
+        }
+        self.join = u;
+    }
+
+    // returns false if the entering edge itself is the blocking edge
+    fn find_leaving_edge(&mut self) -> bool {
+        let (first, second) = if self.state[self.in_edge] == STATE_LOWER { (self.source[self.in_edge], self.target[self.in_edge]) } else { (self.target[self.in_edge], self.source[self.in_edge]) };
+
+        self.delta = self.capacity(self.in_edge);
+        let mut result = 0;
+
+        // search the cycle from the first node to the join node
+        let mut u = first;
+        while u != self.join {
+            let e = self.pred[u];
+            let d = if self.pred_dir[u] == DIR_DOWN { self.capacity(e) - self.flow[e] } else { self.flow[e] };
+            if d < self.delta {
+                self.delta = d;
+                self.u_out = u;
+                result = 1;
+            }
+            u = self.parent[u];
+        }
+
+        // search the cycle from the second node to the join node
+        // `<=` keeps the spanning tree strongly feasible
+        let mut u = second;
+        while u != self.join {
+            let e = self.pred[u];
+            let d = if self.pred_dir[u] == DIR_UP { self.capacity(e) - self.flow[e] } else { self.flow[e] };
+            if d <= self.delta {
+                self.delta = d;
+                self.u_out = u;
+                result = 2;
+            }
+            u = self.parent[u];
+        }
+
+        if result == 1 {
+            self.u_in = first;
+            self.v_in = second;
+        } else {
+            self.u_in = second;
+            self.v_in = first;
+        }
+        result != 0
+    }
+
+    fn change_flow(&mut self, change: bool) {
+        let in_edge = self.in_edge;
+
+        if self.delta > F::zero() {
+            let val = if self.state[in_edge] == STATE_LOWER { self.delta } else { -self.delta };
+            self.flow[in_edge] += val;
+
+            let mut u = self.source[in_edge];
+            while u != self.join {
+                let e = self.pred[u];
+                if self.pred_dir[u] == DIR_UP {
+                    self.flow[e] -= val;
+                } else {
+                    self.flow[e] += val;
+                }
+                u = self.parent[u];
+            }
+
+            let mut u = self.target[in_edge];
+            while u != self.join {
+                let e = self.pred[u];
+                if self.pred_dir[u] == DIR_UP {
+                    self.flow[e] += val;
+                } else {
+                    self.flow[e] -= val;
+                }
+                u = self.parent[u];
+            }
+        }
+
+        if change {
+            self.state[in_edge] = STATE_TREE;
+            let out_edge = self.pred[self.u_out];
+            self.state[out_edge] = if self.flow[out_edge] == F::zero() { STATE_LOWER } else { STATE_UPPER };
+        } else {
+            self.state[in_edge] = -self.state[in_edge];
+        }
+    }
+
+    fn update_tree_structure(&mut self) {
+        let (u_in, v_in, u_out, join) = (self.u_in, self.v_in, self.u_out, self.join);
+        let old_rev_thread = self.rev_thread[u_out];
+        let old_succ_num = self.succ_num[u_out];
+        let old_last_succ = self.last_succ[u_out];
+        let v_out = self.parent[u_out];
+
+        if u_in == u_out {
+            self.parent[u_in] = v_in;
+            self.pred[u_in] = self.in_edge;
+            self.pred_dir[u_in] = if u_in == self.source[self.in_edge] { DIR_UP } else { DIR_DOWN };
+
+            if self.thread[v_in] != u_out {
+                let after = self.thread[old_last_succ];
+                self.thread[old_rev_thread] = after;
+                self.rev_thread[after] = old_rev_thread;
+                let after = self.thread[v_in];
+                self.thread[v_in] = u_out;
+                self.rev_thread[u_out] = v_in;
+                self.thread[old_last_succ] = after;
+                self.rev_thread[after] = old_last_succ;
+            }
+        } else {
+            // old_rev_thread == v_in の場合，joinとv_outは一致する
+            let thread_continue = if old_rev_thread == v_in { self.thread[old_last_succ] } else { self.thread[v_in] };
+
+            // u_inからu_outまでのstem nodesのthreadとparentを更新する
+            let mut stem = u_in;
+            let mut par_stem = v_in;
+            let mut last = self.last_succ[u_in];
+            let mut after = self.thread[last];
+            self.thread[v_in] = u_in;
+            self.dirty_revs.clear();
+            self.dirty_revs.push(v_in);
+            while stem != u_out {
+                // insert the next stem node into the thread list
+                let next_stem = self.parent[stem];
+                self.thread[last] = next_stem;
+                self.dirty_revs.push(last);
+
+                // remove the subtree of stem from the thread list
+                let before = self.rev_thread[stem];
+                self.thread[before] = after;
+                self.rev_thread[after] = before;
+
+                self.parent[stem] = par_stem;
+                par_stem = stem;
+                stem = next_stem;
+
+                last = if self.last_succ[stem] == self.last_succ[par_stem] { self.rev_thread[par_stem] } else { self.last_succ[stem] };
+                after = self.thread[last];
+            }
+            self.parent[u_out] = par_stem;
+            self.thread[last] = thread_continue;
+            self.rev_thread[thread_continue] = last;
+            self.last_succ[u_out] = last;
+
+            if old_rev_thread != v_in {
+                self.thread[old_rev_thread] = after;
+                self.rev_thread[after] = old_rev_thread;
+            }
+
+            for i in 0..self.dirty_revs.len() {
+                let u = self.dirty_revs[i];
+                self.rev_thread[self.thread[u]] = u;
+            }
+
+            // u_outからu_inまでのpred, pred_dir, last_succ, succ_numを更新する
+            let mut tmp_sc = 0;
+            let tmp_ls = self.last_succ[u_out];
+            let mut u = u_out;
+            while u != u_in {
+                let p = self.parent[u];
+                self.pred[u] = self.pred[p];
+                self.pred_dir[u] = -self.pred_dir[p];
+                tmp_sc += self.succ_num[u] - self.succ_num[p];
+                self.succ_num[u] = tmp_sc;
+                self.last_succ[p] = tmp_ls;
+                u = p;
+            }
+            self.pred[u_in] = self.in_edge;
+            self.pred_dir[u_in] = if u_in == self.source[self.in_edge] { DIR_UP } else { DIR_DOWN };
+            self.succ_num[u_in] = old_succ_num;
+        }
+
+        // update last_succ from v_in towards the root
+        let up_limit_out = if self.last_succ[join] == v_in { join } else { NONE };
+        let last_succ_out = self.last_succ[u_out];
+        let mut u = v_in;
+        while u != NONE && self.last_succ[u] == v_in {
+            self.last_succ[u] = last_succ_out;
+            u = self.parent[u];
+        }
+
+        // update last_succ from v_out towards the root
+        if join != old_rev_thread && v_in != old_rev_thread {
+            let mut u = v_out;
+            while u != NONE && u != up_limit_out && self.last_succ[u] == old_last_succ {
+                self.last_succ[u] = old_rev_thread;
+                u = self.parent[u];
+            }
+        } else if last_succ_out != old_last_succ {
+            let mut u = v_out;
+            while u != NONE && u != up_limit_out && self.last_succ[u] == old_last_succ {
+                self.last_succ[u] = last_succ_out;
+                u = self.parent[u];
+            }
+        }
+
+        // update succ_num from v_in to join
+        let mut u = v_in;
+        while u != join {
+            self.succ_num[u] += old_succ_num;
+            u = self.parent[u];
+        }
+        // update succ_num from v_out to join
+        let mut u = v_out;
+        while u != join {
+            self.succ_num[u] -= old_succ_num;
+            u = self.parent[u];
+        }
+    }
+
+    // make the reduced cost of the entering edge zero by shifting the potentials of the subtree of u_in
+    fn update_potential(&mut self) {
+        let c = self.cost[self.in_edge];
+        let sigma = if self.pred_dir[self.u_in] == DIR_UP { self.potentials[self.v_in] - self.potentials[self.u_in] - c } else { self.potentials[self.v_in] - self.potentials[self.u_in] + c };
+
+        let end = self.thread[self.last_succ[self.u_in]];
+        let mut u = self.u_in;
+        while u != end {
+            self.potentials[u] += sigma;
+            u = self.thread[u];
+        }
+    }
+}
+
+impl<F: Flow> MinCostFlowSolver<F> for NetworkSimplex<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        NetworkSimplex::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        NetworkSimplex::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        NetworkSimplex::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        NetworkSimplex::solve(self)
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        NetworkSimplex::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        NetworkSimplex::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        NetworkSimplex::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+}
@@ -0,0 +1,134 @@
+use minimum_cost_flow_common::{EdgeId, MinCostFlowProblem, Status};
+use network_simplex::NetworkSimplex;
+
+// 0 -> 1 -> 2 -> 3 and 0 -> 2, 1 -> 3. the optimum sends 2 along 0 -> 2 -> 3 and 2 along 0 -> 1 -> 2 -> 3
+fn diamond() -> MinCostFlowProblem<i64> {
+    let mut problem = MinCostFlowProblem::new(4);
+    problem.add_directed_edge(0, 1, 0, 4, 2);
+    problem.add_directed_edge(0, 2, 0, 2, 2);
+    problem.add_directed_edge(1, 2, 0, 2, 1);
+    problem.add_directed_edge(1, 3, 0, 3, 3);
+    problem.add_directed_edge(2, 3, 0, 5, 1);
+    problem.add_supply(0, 4);
+    problem.add_supply(3, -4);
+    problem
+}
+
+#[test]
+fn solves_diamond() {
+    let problem = diamond();
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.cost, Some(14));
+    assert_eq!(solution.flows, vec![2, 2, 2, 0, 4]);
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn solves_lower_bounds_and_negative_costs() {
+    // the negative cycle 0 -> 1 -> 2 -> 0 is filled up to the capacity of 1 -> 2, and 2 -> 0 must carry at least 1
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, 5, -3);
+    problem.add_directed_edge(1, 2, -1, 3, 1);
+    problem.add_directed_edge(2, 0, 1, 4, 1);
+    problem.add_directed_edge(1, 1, -2, 2, -1);
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.cost, Some(-3 * 3 + 3 + 3 - 2));
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn solve_is_repeatable() {
+    let problem = diamond();
+    let (mut solver, edge_ids) = problem.build::<NetworkSimplex<i64>>();
+    assert_eq!(solver.solve(), Status::Optimal);
+    let num_pivot = solver.num_pivot();
+    let flows: Vec<i64> = edge_ids.iter().map(|&e| solver.get_directed_edge(e).flow).collect();
+
+    // the pivots are counted for each solve, and the same problem gives the same flow
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.num_pivot(), num_pivot);
+    assert_eq!(solver.optimal_cost(), Some(14));
+    assert_eq!(edge_ids.iter().map(|&e| solver.get_directed_edge(e).flow).collect::<Vec<_>>(), flows);
+
+    // more supply needs the more expensive 0 -> 1 -> 3
+    solver.add_supply(0, 1);
+    solver.add_supply(3, -1);
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(19));
+    assert_eq!(solver.get_directed_edge(EdgeId::new(3)).flow, 1);
+}
+
+#[test]
+fn reports_infeasible_and_unbalanced() {
+    let mut problem = diamond();
+    problem.add_supply(0, 4);
+    problem.add_supply(3, -4);
+    assert_eq!(problem.solve::<NetworkSimplex<i64>>().status, Status::Infeasible);
+
+    problem.add_supply(0, 1);
+    assert_eq!(problem.solve::<NetworkSimplex<i64>>().status, Status::Unbalanced);
+}
+
+#[test]
+fn reports_bad_cost_range() {
+    // the artificial edges cost (gamma + 1) * n, and the potentials up to 6 times that
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, 1, i64::MAX / 12);
+    problem.add_supply(0, 1);
+    problem.add_supply(1, -1);
+    assert_eq!(problem.solve::<NetworkSimplex<i64>>().status, Status::BadCostRange);
+
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, 1, i64::MAX / 12 - 1);
+    problem.add_supply(0, 1);
+    problem.add_supply(1, -1);
+    assert_eq!(problem.solve::<NetworkSimplex<i64>>().status, Status::Optimal);
+}
+
+#[test]
+fn adds_edges_after_solve() {
+    let problem = diamond();
+    let (mut solver, _) = problem.build::<NetworkSimplex<i64>>();
+    assert_eq!(solver.solve(), Status::Optimal);
+
+    // a cheaper edge 0 -> 3 takes 3 of the 4 units, and the last one goes along 0 -> 2 -> 3
+    let e = solver.add_directed_edge(0, 3, 0, 3, 1);
+    let edge = solver.get_directed_edge(e);
+    assert_eq!((edge.from, edge.to, edge.flow, edge.upper, edge.cost), (0, 3, 0, 3, 1));
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(3 + 3));
+    assert_eq!(solver.get_directed_edge(e).flow, 3);
+
+    // and after an infeasible solve
+    solver.add_supply(0, 10);
+    solver.add_supply(3, -10);
+    assert_eq!(solver.solve(), Status::Infeasible);
+    let e = solver.add_directed_edge(0, 3, 0, 10, 5);
+    assert_eq!(solver.get_directed_edge(e).to, 3);
+    // 3 units at 1, 2 at 3 and 2 at 4 as before, and the other 7 at 5
+    assert_eq!(solver.solve(), Status::Optimal);
+    assert_eq!(solver.optimal_cost(), Some(3 + 2 * 3 + 2 * 4 + 7 * 5));
+}
+
+#[test]
+fn solves_up_to_the_full_range() {
+    let mut problem = MinCostFlowProblem::new(2);
+    // the negative cycle 0 -> 1 -> 0 carries i64::MAX units
+    problem.add_directed_edge(0, 1, -1, i64::MAX - 1, -1);
+    problem.add_directed_edge(1, 0, 0, i64::MAX, 0);
+    problem.add_supply(0, 5);
+    problem.add_supply(1, -5);
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.flows, vec![i64::MAX - 1, i64::MAX - 6]);
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "upper - lower must be at most F::max_value()")]
+fn rejects_ranges_beyond_max_value() {
+    let mut solver = NetworkSimplex::<i64>::new(2);
+    solver.add_directed_edge(0, 1, -1, i64::MAX, 1);
+}
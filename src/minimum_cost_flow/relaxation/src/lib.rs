@@ -0,0 +1,390 @@
+#![allow(clippy::needless_range_loop)]
+use push_relabel::LowerBound;
+
+pub use minimum_cost_flow_common::{Edge, EdgeId, Flow, MinCostFlowSolver, Status};
+
+#[derive(Clone)]
+struct InternalEdge<F: Flow> {
+    to: usize,
+    rev: usize, // 逆辺のindex. graph[to][rev]でアクセスできる
+    flow: F,
+    lower: F,
+    upper: F,
+    cost: F,
+}
+
+impl<F: Flow> InternalEdge<F> {
+    pub fn new(to: usize, rev: usize, flow: F, lower: F, upper: F, cost: F) -> Self {
+        InternalEdge { to, rev, flow, lower, upper, cost }
+    }
+
+    pub fn residual_capacity(&self) -> F {
+        self.upper - self.flow
+    }
+}
+
+// Bertsekas-Tseng relaxation (dual ascent)
+// flows and potentials always satisfy complementary slackness, i.e. every residual edge has a non-negative reduced cost
+// each iteration grows a set S from a node with positive excess along balanced edges (reduced cost 0) and
+// either augments flow to a node with negative excess or lowers the potentials of S when it is a dual ascent direction
+pub struct Relaxation<F: Flow> {
+    num_of_nodes: usize,
+    graph: Vec<Vec<InternalEdge<F>>>,
+    edges: Vec<(usize, usize)>, // graph[u][i] of each edge in the order of addition
+
+    // Node
+    initial_excess: Vec<F>,
+    excess: Vec<i128>, // the initial flow can move more than F::max_value() through a node
+    potentials: Vec<F>,
+
+    // Edge
+    is_rev: Vec<Vec<bool>>,
+
+    // status
+    status: Status,
+    optimal_cost: Option<i128>,
+    num_augment: u64,
+    num_price_change: u64,
+}
+
+impl<F: Flow> Relaxation<F> {
+    pub fn new(num_of_nodes: usize) -> Self {
+        Relaxation {
+            num_of_nodes,
+            graph: vec![vec![]; num_of_nodes],
+            edges: Vec::new(),
+
+            initial_excess: vec![F::zero(); num_of_nodes],
+            excess: vec![0; num_of_nodes],
+            potentials: vec![F::zero(); num_of_nodes],
+
+            is_rev: vec![vec![]; num_of_nodes],
+
+            status: Status::NotSolved,
+            optimal_cost: None,
+            num_augment: 0,
+            num_price_change: 0,
+        }
+    }
+
+    pub fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        assert!(lower <= upper);
+        assert!(from < self.num_of_nodes);
+        assert!(to < self.num_of_nodes);
+
+        let e = self.graph[from].len();
+        let re = if from == to { e + 1 } else { self.graph[to].len() };
+
+        let e1 = InternalEdge::new(to, re, F::zero(), lower, upper, cost);
+        self.graph[from].push(e1);
+        self.is_rev[from].push(false);
+
+        let e2 = InternalEdge::new(from, e, F::zero(), F::zero(), -lower, -cost);
+        self.graph[to].push(e2);
+        self.is_rev[to].push(true);
+
+        self.edges.push((from, e));
+        EdgeId::new(self.edges.len() - 1)
+    }
+
+    pub fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        let (u, i) = self.edges[edge_id.index()];
+        let e = &self.graph[u][i];
+        Edge { from: u, to: e.to, flow: e.flow, lower: e.lower, upper: e.upper, cost: e.cost }
+    }
+
+    pub fn get_potential(&self) -> Vec<F> {
+        self.potentials.clone()
+    }
+
+    pub fn add_supply(&mut self, node: usize, supply: F) {
+        self.initial_excess[node] += supply;
+    }
+
+    pub fn solve(&mut self) -> Status {
+        self.status = Status::NotSolved;
+        self.optimal_cost = None;
+
+        if self.num_of_nodes == 0 {
+            self.status = Status::Optimal;
+            self.optimal_cost = Some(0);
+            return Status::Optimal;
+        }
+
+        if self.is_unbalanced() {
+            self.status = Status::Unbalanced;
+            return Status::Unbalanced;
+        }
+
+        // 実行不可能なときは双対問題が非有界になり，relaxationが止まらない
+        if self.is_infeasible() {
+            self.status = Status::Infeasible;
+            return Status::Infeasible;
+        }
+
+        self.initialize();
+
+        let mut in_s = vec![false; self.num_of_nodes];
+        let mut labeled = vec![false; self.num_of_nodes];
+        loop {
+            let mut done = true;
+            for s in 0..self.num_of_nodes {
+                while self.excess[s] > 0 {
+                    done = false;
+                    if !self.relaxation_iteration(s, &mut in_s, &mut labeled) {
+                        self.status = Status::Infeasible;
+                        return Status::Infeasible;
+                    }
+                }
+            }
+            if done {
+                break;
+            }
+        }
+
+        let mut cost = 0;
+        for u in 0..self.num_of_nodes {
+            for (i, edge) in self.graph[u].iter().enumerate() {
+                if !self.is_rev[u][i] {
+                    cost += F::to_i128(&edge.flow).unwrap() * F::to_i128(&edge.cost).unwrap();
+                }
+            }
+        }
+        self.optimal_cost = Some(cost);
+
+        self.status = Status::Optimal;
+        Status::Optimal
+    }
+
+    pub fn optimal_cost(&self) -> Option<i128> {
+        self.optimal_cost
+    }
+
+    pub fn num_augment(&self) -> u64 {
+        self.num_augment
+    }
+
+    pub fn num_price_change(&self) -> u64 {
+        self.num_price_change
+    }
+
+    fn is_unbalanced(&self) -> bool {
+        let mut total = F::zero();
+        for u in 0..self.num_of_nodes {
+            total += self.initial_excess[u];
+        }
+        total != F::zero()
+    }
+
+    fn is_infeasible(&self) -> bool {
+        let mut solver = LowerBound::new(self.num_of_nodes);
+
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                let edge = &self.graph[u][i];
+                if !self.is_rev[u][i] {
+                    solver.add_edge(u, edge.to, edge.lower, edge.upper);
+                }
+            }
+        }
+
+        for u in 0..self.num_of_nodes {
+            solver.add_supply(u, self.initial_excess[u]);
+        }
+        solver.solve().is_none()
+    }
+
+    // potentials = 0 and the flow of each edge is decided by the sign of its cost
+    fn initialize(&mut self) {
+        self.excess = self.initial_excess.iter().map(|e| F::to_i128(e).unwrap()).collect();
+        self.potentials = vec![F::zero(); self.num_of_nodes];
+        self.num_augment = 0;
+        self.num_price_change = 0;
+
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                self.graph[u][i].flow = F::zero();
+            }
+        }
+        for u in 0..self.num_of_nodes {
+            for i in 0..self.graph[u].len() {
+                if !self.is_rev[u][i] {
+                    let edge = &self.graph[u][i];
+                    let flow = if edge.cost < F::zero() { edge.upper } else { edge.lower };
+                    self.push_flow(u, i, flow);
+                }
+            }
+        }
+    }
+
+    // returns false if the problem turns out to be infeasible
+    fn relaxation_iteration(&mut self, s: usize, in_s: &mut [bool], labeled: &mut [bool]) -> bool {
+        let mut queue = vec![s];
+        let mut pred: Vec<Option<(usize, usize)>> = vec![None; self.num_of_nodes];
+        labeled[s] = true;
+
+        // ascent: sum of excess in S - residual capacity of balanced edges leaving S
+        // the capacities can be up to F::max_value(), so it is summed in i128
+        let mut ascent = 0;
+        let mut head = 0;
+        let mut result = None;
+        while head < queue.len() {
+            let u = queue[head];
+            head += 1;
+
+            // add u to S
+            ascent += self.excess[u];
+            for edge in self.graph[u].iter() {
+                let v = edge.to;
+                if v == u || self.reduced_cost(u, edge) != F::zero() {
+                    continue;
+                }
+                if in_s[v] {
+                    // v -> u はもうSから出る辺ではない
+                    ascent += F::to_i128(&self.graph[v][edge.rev].residual_capacity()).unwrap();
+                } else {
+                    ascent -= F::to_i128(&edge.residual_capacity()).unwrap();
+                }
+            }
+            in_s[u] = true;
+
+            if ascent > 0 {
+                result = Some(self.price_change(&queue[..head], in_s));
+                break;
+            }
+
+            // scan u
+            for (i, edge) in self.graph[u].iter().enumerate() {
+                let v = edge.to;
+                if labeled[v] || edge.residual_capacity() <= F::zero() || self.reduced_cost(u, edge) != F::zero() {
+                    continue;
+                }
+                labeled[v] = true;
+                pred[v] = Some((u, i));
+                queue.push(v);
+
+                if self.excess[v] < 0 {
+                    result = Some(self.augment(v, &pred));
+                    break;
+                }
+            }
+            if result.is_some() {
+                break;
+            }
+        }
+
+        for &u in queue.iter() {
+            in_s[u] = false;
+            labeled[u] = false;
+        }
+
+        // Sのすべてのnodeを調べたときは必ずascent > 0になる
+        result.unwrap()
+    }
+
+    // augment flow along balanced edges from the root of the labeling tree to t
+    fn augment(&mut self, t: usize, pred: &[Option<(usize, usize)>]) -> bool {
+        let mut flow = -self.excess[t];
+        let mut v = t;
+        while let Some((u, i)) = pred[v] {
+            flow = i128::min(flow, F::to_i128(&self.graph[u][i].residual_capacity()).unwrap());
+            v = u;
+        }
+        // at most a residual capacity, so it fits in F
+        let flow = F::from_i128(i128::min(flow, self.excess[v])).unwrap();
+
+        let mut v = t;
+        while let Some((u, i)) = pred[v] {
+            self.push_flow(u, i, flow);
+            v = u;
+        }
+        self.num_augment += 1;
+        true
+    }
+
+    // saturate the balanced edges leaving S and lower the potentials of S until a new edge becomes balanced
+    fn price_change(&mut self, nodes: &[usize], in_s: &[bool]) -> bool {
+        let mut gamma = None;
+        for &u in nodes {
+            for i in 0..self.graph[u].len() {
+                let edge = &self.graph[u][i];
+                if in_s[edge.to] || edge.residual_capacity() <= F::zero() {
+                    continue;
+                }
+
+                let reduced_cost = self.reduced_cost(u, edge);
+                if reduced_cost == F::zero() {
+                    let flow = edge.residual_capacity();
+                    self.push_flow(u, i, flow);
+                } else if gamma.is_none_or(|g| reduced_cost < g) {
+                    gamma = Some(reduced_cost);
+                }
+            }
+        }
+
+        // Sから出る残余辺がないのに余剰が残っている
+        let gamma = match gamma {
+            Some(gamma) => gamma,
+            None => return false,
+        };
+
+        for &u in nodes {
+            self.potentials[u] -= gamma;
+        }
+        self.num_price_change += 1;
+        true
+    }
+
+    fn push_flow(&mut self, u: usize, i: usize, flow: F) {
+        if flow == F::zero() {
+            return;
+        }
+
+        let to = self.graph[u][i].to;
+        let from = u;
+        let rev = self.graph[u][i].rev;
+
+        self.graph[u][i].flow += flow;
+        self.graph[to][rev].flow -= flow;
+        self.excess[from] -= F::to_i128(&flow).unwrap();
+        self.excess[to] += F::to_i128(&flow).unwrap();
+    }
+
+    fn reduced_cost(&self, u: usize, edge: &InternalEdge<F>) -> F {
+        edge.cost + self.potentials[u] - self.potentials[edge.to]
+    }
+}
+
+impl<F: Flow> MinCostFlowSolver<F> for Relaxation<F> {
+    fn new(num_of_nodes: usize) -> Self {
+        Relaxation::new(num_of_nodes)
+    }
+
+    fn add_directed_edge(&mut self, from: usize, to: usize, lower: F, upper: F, cost: F) -> EdgeId {
+        Relaxation::add_directed_edge(self, from, to, lower, upper, cost)
+    }
+
+    fn add_supply(&mut self, node: usize, supply: F) {
+        Relaxation::add_supply(self, node, supply)
+    }
+
+    fn solve(&mut self) -> Status {
+        Relaxation::solve(self)
+    }
+
+    fn get_directed_edge(&self, edge_id: EdgeId) -> Edge<F> {
+        Relaxation::get_directed_edge(self, edge_id)
+    }
+
+    fn get_potential(&self) -> Vec<F> {
+        Relaxation::get_potential(self)
+    }
+
+    fn optimal_cost(&self) -> Option<i128> {
+        Relaxation::optimal_cost(self)
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+}
@@ -0,0 +1,62 @@
+use minimum_cost_flow_common::{MinCostFlowProblem, Status};
+use relaxation::Relaxation;
+
+#[test]
+fn solves_max_capacity_negative_cycle() {
+    // the cycle 0 -> 1 -> 0 is saturated, and the balanced edges leaving S have a capacity of i64::MAX
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, i64::MAX, -1);
+    problem.add_directed_edge(1, 0, 0, i64::MAX, -1);
+    problem.add_directed_edge(0, 2, 0, 10, 1);
+    problem.add_supply(0, 5);
+    problem.add_supply(2, -5);
+
+    let solution = problem.solve::<Relaxation<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.cost, Some(-18446744073709551609));
+    assert_eq!(solution.flows, vec![i64::MAX, i64::MAX, 5]);
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn solves_initial_excess_beyond_max() {
+    // the initial flow saturates both edges out of 0 before the edges back into it
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, i64::MAX, -1);
+    problem.add_directed_edge(0, 2, 0, i64::MAX, -1);
+    problem.add_directed_edge(1, 0, 0, i64::MAX, 0);
+    problem.add_directed_edge(2, 0, 0, i64::MAX - 1, 0);
+
+    let solution = problem.solve::<Relaxation<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.cost, Some(-2 * i64::MAX as i128 + 1));
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn solves_lower_bounds_and_negative_costs() {
+    let mut problem = MinCostFlowProblem::new(3);
+    problem.add_directed_edge(0, 1, 0, 5, -3);
+    problem.add_directed_edge(1, 2, -1, 3, 1);
+    problem.add_directed_edge(2, 0, 1, 4, 1);
+    problem.add_directed_edge(1, 1, -2, 2, -1);
+    problem.add_supply(0, 2);
+    problem.add_supply(2, -2);
+
+    let solution = problem.solve::<Relaxation<i64>>();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.cost, Some(-3 * 3 + 3 + 1 - 2));
+    problem.check_solution(&solution).unwrap();
+}
+
+#[test]
+fn reports_infeasible_and_unbalanced() {
+    let mut problem = MinCostFlowProblem::new(2);
+    problem.add_directed_edge(0, 1, 0, 3, 1);
+    problem.add_supply(0, 4);
+    problem.add_supply(1, -4);
+    assert_eq!(problem.solve::<Relaxation<i64>>().status, Status::Infeasible);
+
+    problem.add_supply(1, 1);
+    assert_eq!(problem.solve::<Relaxation<i64>>().status, Status::Unbalanced);
+}
@@ -0,0 +1,93 @@
+// helpers shared by the tests of the minimum cost flow crates: random tiny networks, and their optimal cost by
+// exhaustive enumeration of the flows
+use minimum_cost_flow_common::{MinCostFlowProblem, Status};
+
+// xorshift64, so that the tests are deterministic without extra dependencies
+pub struct Random(pub u64);
+
+impl Random {
+    // a random integer in [a, b]
+    pub fn next(&mut self, a: i64, b: i64) -> i64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        a + (self.0 % (b - a + 1) as u64) as i64
+    }
+}
+
+// a tiny network (at most 4 nodes, 6 edges and 4 flow values per edge) with lower bounds (possibly negative),
+// negative costs, self loops and parallel edges
+pub fn random_problem(random: &mut Random) -> MinCostFlowProblem<i64> {
+    let n = random.next(1, 4) as usize;
+    let m = random.next(0, 6) as usize;
+    let mut problem = MinCostFlowProblem::new(n);
+    let mut flows = Vec::new();
+    for _ in 0..m {
+        let from = random.next(0, n as i64 - 1) as usize;
+        let to = random.next(0, n as i64 - 1) as usize;
+        let lower = random.next(-2, 2);
+        let upper = lower + random.next(0, 3);
+        let cost = random.next(-5, 5);
+        problem.add_directed_edge(from, to, lower, upper, cost);
+        flows.push(random.next(lower, upper));
+    }
+
+    // the supplies of a random flow, so that most instances are feasible
+    for (e, &flow) in problem.edges.clone().iter().zip(flows.iter()) {
+        problem.add_supply(e.from, flow);
+        problem.add_supply(e.to, -flow);
+    }
+    match random.next(0, 7) {
+        0 => {
+            let u = random.next(0, n as i64 - 1) as usize;
+            problem.add_supply(u, random.next(1, 2));
+        }
+        1 => {
+            let u = random.next(0, n as i64 - 1) as usize;
+            let v = random.next(0, n as i64 - 1) as usize;
+            problem.add_supply(u, 3);
+            problem.add_supply(v, -3);
+        }
+        _ => {}
+    }
+    problem
+}
+
+// the status and the cost of an optimal flow by trying every flow
+pub fn brute_force(problem: &MinCostFlowProblem<i64>) -> (Status, Option<i128>) {
+    if problem.supply.iter().sum::<i64>() != 0 {
+        return (Status::Unbalanced, None);
+    }
+
+    let mut best = None;
+    let mut flows: Vec<i64> = problem.edges.iter().map(|e| e.lower).collect();
+    loop {
+        let mut balance = problem.supply.clone();
+        for (e, &flow) in problem.edges.iter().zip(flows.iter()) {
+            balance[e.from] -= flow;
+            balance[e.to] += flow;
+        }
+        if balance.iter().all(|&b| b == 0) {
+            let cost = problem.cost(&flows);
+            if best.is_none_or(|best| cost < best) {
+                best = Some(cost);
+            }
+        }
+
+        // next combination
+        let mut i = 0;
+        while i < flows.len() && flows[i] == problem.edges[i].upper {
+            flows[i] = problem.edges[i].lower;
+            i += 1;
+        }
+        if i == flows.len() {
+            break;
+        }
+        flows[i] += 1;
+    }
+
+    match best {
+        Some(cost) => (Status::Optimal, Some(cost)),
+        None => (Status::Infeasible, None),
+    }
+}
@@ -0,0 +1,37 @@
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+
+// usage: dimacs-check_solution <instance> <solution>
+// compares a solution file (e.g. written by CS2 or LEMON) with the instance
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <instance> <solution>", args[0]);
+        process::exit(2);
+    }
+
+    let problem = match dimacs::read_problem::<i64, _>(BufReader::new(File::open(&args[1]).unwrap())) {
+        Ok(problem) => problem,
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(2);
+        }
+    };
+    let solution = match dimacs::read_solution(&problem, BufReader::new(File::open(&args[2]).unwrap())) {
+        Ok(solution) => solution,
+        Err(e) => {
+            eprintln!("{}: {}", args[2], e);
+            process::exit(2);
+        }
+    };
+
+    match problem.check_solution(&solution) {
+        Ok(()) => println!("ok {}", solution.cost.unwrap()),
+        Err(e) => {
+            println!("ng {}", e);
+            process::exit(1);
+        }
+    }
+}
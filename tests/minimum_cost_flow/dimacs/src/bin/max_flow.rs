@@ -0,0 +1,38 @@
+use cost_scaling_push_relabel::{CostScalingPushRelabel, Status};
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+use std::time::Instant;
+
+// usage: dimacs-max_flow <instance>
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("usage: {} <instance>", args[0]);
+        process::exit(2);
+    }
+
+    let problem = match dimacs::read_max_flow_problem::<i64, _>(BufReader::new(File::open(&args[1]).unwrap())) {
+        Ok(problem) => problem,
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(2);
+        }
+    };
+
+    let mut solver = CostScalingPushRelabel::new(problem.num_of_nodes);
+    for &(from, to, upper) in problem.edges.iter() {
+        solver.add_directed_edge(from, to, 0, upper, 0);
+    }
+
+    let start = Instant::now();
+    let (status, flow) = solver.solve_max_flow_with_min_cost(problem.source, problem.sink);
+    let end = start.elapsed();
+    println!("{}.{:03}", end.as_secs(), end.subsec_millis());
+
+    match status {
+        Status::Optimal => println!("{}", flow),
+        _ => println!("{:?}", status),
+    }
+}
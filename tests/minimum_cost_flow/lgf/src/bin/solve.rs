@@ -0,0 +1,30 @@
+use network_simplex::{NetworkSimplex, Status};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+
+// usage: lgf-solve <instance>
+// writes the network with flow and potential maps to stdout
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("usage: {} <instance>", args[0]);
+        process::exit(2);
+    }
+
+    let problem = match lgf::read_problem::<i64, _>(BufReader::new(File::open(&args[1]).unwrap())) {
+        Ok(problem) => problem,
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(2);
+        }
+    };
+
+    let solution = problem.solve::<NetworkSimplex<i64>>();
+    if solution.status != Status::Optimal {
+        println!("{:?}", solution.status);
+        process::exit(1);
+    }
+    lgf::write_solution(&problem, &solution, io::stdout().lock()).unwrap();
+}
@@ -0,0 +1,42 @@
+use network_simplex::{NetworkSimplex, Status};
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
+
+// usage: lp-check_solution <instance> <solution>
+// validates the solution of an LP solver and compares its cost with the network simplex
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <instance> <solution>", args[0]);
+        process::exit(2);
+    }
+
+    let problem = match dimacs::read_problem::<i64, _>(BufReader::new(File::open(&args[1]).unwrap())) {
+        Ok(problem) => problem,
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(2);
+        }
+    };
+    let solution = match lp::read_solution(&problem, BufReader::new(File::open(&args[2]).unwrap())) {
+        Ok(solution) => solution,
+        Err(e) => {
+            eprintln!("{}: {}", args[2], e);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = problem.check_solution(&solution) {
+        println!("ng {}", e);
+        process::exit(1);
+    }
+
+    let expected = problem.solve::<NetworkSimplex<i64>>();
+    if expected.status != Status::Optimal || expected.cost != solution.cost {
+        println!("ng cost is {} but the network simplex returns {:?} {:?}", solution.cost.unwrap(), expected.status, expected.cost);
+        process::exit(1);
+    }
+    println!("ok {}", solution.cost.unwrap());
+}
@@ -0,0 +1,24 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::process;
+
+// usage: lp-export <instance> <lp file> <mps file>
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        eprintln!("usage: {} <instance> <lp file> <mps file>", args[0]);
+        process::exit(2);
+    }
+
+    let problem = match dimacs::read_problem::<i64, _>(BufReader::new(File::open(&args[1]).unwrap())) {
+        Ok(problem) => problem,
+        Err(e) => {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(2);
+        }
+    };
+
+    lp::write_lp(&problem, BufWriter::new(File::create(&args[2]).unwrap())).unwrap();
+    lp::write_mps(&problem, BufWriter::new(File::create(&args[3]).unwrap())).unwrap();
+}